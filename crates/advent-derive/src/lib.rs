@@ -0,0 +1,147 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `From<&str>` for a struct from a description of its input's shape.
+///
+/// On the struct, `#[parse(delimiter = ",")]` reads one record whose fields are split on the
+/// delimiter and parsed with `FromStr`, and `#[parse(sections)]` gives each field its own
+/// blank-line-delimited section. Otherwise every field is built from the whole input.
+///
+/// On a `Vec` field, `#[parse(lines)]` builds one item per non-blank line with `From<&str>`.
+/// Any other field is built from its text with `From<&str>`.
+#[proc_macro_derive(ParseInput, attributes(parse))]
+pub fn derive_parse_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+enum Layout {
+    Whole,
+    Delimited(LitStr),
+    Sections,
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    lines: bool,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(name, "ParseInput needs a struct with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(name, "ParseInput can only be derived for structs")),
+    };
+
+    let layout = struct_layout(&input.attrs)?;
+    let idents = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+    let mut values = Vec::new();
+
+    for field in fields {
+        let options = field_options(&field.attrs)?;
+        let ident = field.ident.as_ref().unwrap();
+
+        let value = match &layout {
+            Layout::Delimited(_) if options.lines => {
+                return Err(syn::Error::new_spanned(ident, "`lines` cannot be used on a delimited field"));
+            }
+            Layout::Delimited(_) => {
+                let missing = format!("Missing field `{}`", ident);
+                let invalid = format!("Unable to parse field `{}`", ident);
+                quote! { parts.next().expect(#missing).parse().expect(#invalid) }
+            }
+            Layout::Whole => field_value(&format_ident!("s"), &options),
+            Layout::Sections => field_value(&format_ident!("section_{}", ident), &options),
+        };
+        values.push(value);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let construct = quote! { #name { #(#idents: #values),* } };
+
+    let body = match &layout {
+        Layout::Whole => construct,
+        Layout::Delimited(delimiter) => {
+            let extra = format!("Too many fields for {}", name);
+            quote! {
+                let mut parts = s.trim().split(#delimiter).map(str::trim);
+                let parsed = #construct;
+                assert!(parts.next().is_none(), #extra);
+                parsed
+            }
+        }
+        Layout::Sections => {
+            let sections = idents.iter().map(|i| format_ident!("section_{}", i)).collect::<Vec<_>>();
+            let count = idents.len();
+            let message = format!("Expected {} sections for {}", count, name);
+            quote! {
+                let [#(#sections),*] = ::advent::parse::split_sections_exact::<#count>(s).expect(#message);
+                let [#(#sections),*] = [#(#sections.text),*];
+                #construct
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::convert::From<&str> for #name #ty_generics #where_clause {
+            fn from(s: &str) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+fn field_value(text: &syn::Ident, options: &FieldOptions) -> TokenStream2 {
+    if options.lines {
+        quote! {
+            #text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| ::std::convert::From::from(line.trim_end()))
+                .collect()
+        }
+    } else {
+        quote! { ::std::convert::From::from(#text) }
+    }
+}
+
+fn struct_layout(attrs: &[Attribute]) -> syn::Result<Layout> {
+    let mut layout = Layout::Whole;
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("delimiter") {
+                layout = Layout::Delimited(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("sections") {
+                layout = Layout::Sections;
+                Ok(())
+            } else {
+                Err(meta.error("expected `delimiter = \"...\"` or `sections`"))
+            }
+        })?;
+    }
+
+    Ok(layout)
+}
+
+fn field_options(attrs: &[Attribute]) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("lines") {
+                options.lines = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `lines`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
@@ -0,0 +1,83 @@
+// Solves every day of a year at once. Each part runs as its own job, so with
+// `--features parallel` the slow parts overlap instead of queueing behind each other.
+
+use advent::config::Config;
+use advent::run::Day;
+use std::process::ExitCode;
+
+// Every year in the workspace, oldest first
+const YEARS: &[(u32, &[Day])] = &[
+    (advent2025::YEAR, advent2025::DAYS),
+];
+
+const USAGE: &str = "usage: all [--year <year>] [--input-dir <dir>] [--log <level>] [--visualize] [<day>...]";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let (config, only) = match Config::load().and_then(|mut c| c.apply_args(&args).map(|rest| (c, rest))) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(only) = parse_days(&only) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let Some(&(year, days)) = YEARS.iter().find(|&&(y, _)| y == config.year) else {
+        eprintln!("no solutions for {}; years: {:?}", config.year, YEARS.iter().map(|y| y.0).collect::<Vec<_>>());
+        return ExitCode::FAILURE;
+    };
+
+    let days = days.iter()
+        .filter(|d| only.is_empty() || only.contains(&d.day))
+        .collect::<Vec<_>>();
+
+    let mut jobs = Vec::new();
+    for day in &days {
+        match day.read_input(&config, year) {
+            Ok(input) => {
+                day.visualize(&config, &input);
+                jobs.extend([(day.part1, input.clone()), (day.part2, input)]);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let start = std::time::Instant::now();
+    let answers = advent::par::map(&jobs, |(part, input)| part(input));
+    let elapsed = start.elapsed();
+
+    for (day, parts) in days.iter().zip(answers.chunks(2)) {
+        println!("{} {}: {} {}", year, day.name(), parts[0], parts[1]);
+    }
+    println!("{} days in {:?} ({})", days.len(), elapsed, if advent::par::enabled() { "parallel" } else { "sequential" });
+
+    ExitCode::SUCCESS
+}
+
+// Day numbers, given as `7`, `07` or `day07`
+fn parse_days(args: &[String]) -> Option<Vec<u32>> {
+    args.iter().map(|arg| arg.trim_start_matches("day").parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days(&args("")), Some(vec![]));
+        assert_eq!(parse_days(&args("7 day08 09")), Some(vec![7, 8, 9]));
+        assert_eq!(parse_days(&args("seven")), None);
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Where to fetch from, who to fetch as, and where the results are kept.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub base_url: String,
+    pub year: u32,
+    pub session: Option<String>,
+    /// Shortest gap allowed between the starts of two requests to the site.
+    pub min_interval: Duration,
+    pub input_dir: PathBuf,
+    pub problem_dir: PathBuf,
+    pub answer_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::for_year(2025)
+    }
+}
+
+impl Config {
+    /// The defaults for a year, keeping its files under `years/<year>` in the workspace.
+    pub fn for_year(year: u32) -> Self {
+        let dir = PathBuf::from("years").join(year.to_string());
+
+        Config {
+            base_url: "https://adventofcode.com".to_owned(),
+            year,
+            session: None,
+            min_interval: Duration::from_secs(5),
+            input_dir: dir.join("input"),
+            problem_dir: dir.join("problem"),
+            answer_dir: dir.join("answer"),
+        }
+    }
+
+    /// The directories for a year from the project settings, with the session token taken
+    /// from `AOC_SESSION` or else from a `.aoc-session` file beside `aoc.toml`.
+    pub fn load(project: &advent::config::Config, year: u32) -> Self {
+        let session = std::env::var("AOC_SESSION").ok()
+            .or_else(|| fs::read_to_string(project.root.join(".aoc-session")).ok())
+            .map(|s| s.trim().to_owned())
+            .filter(|s| !s.is_empty());
+
+        Config {
+            session,
+            input_dir: project.input_dir(year),
+            problem_dir: project.problem_dir(year),
+            answer_dir: project.answer_dir(year),
+            ..Config::for_year(year)
+        }
+    }
+
+    /// Where the day's puzzle page is saved, as `NN.html` in the problem directory.
+    pub fn puzzle_path(&self, day: u32) -> PathBuf {
+        self.problem_dir.join(format!("{:02}.html", day))
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Status(u16, String),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(f, "no session token; set AOC_SESSION or write it to .aoc-session"),
+            FetchError::Status(code, body) => write!(f, "server answered {}: {}", code, body.trim()),
+            FetchError::Transport(e) => write!(f, "request failed: {}", e),
+            FetchError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> Self {
+        FetchError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for FetchError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, response) => FetchError::Status(code, response.into_string().unwrap_or_default()),
+            ureq::Error::Transport(t) => FetchError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// A file that is now on disk, and whether it was already there.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    pub cached: bool,
+}
+
+/// Fetches puzzle files for one year, keeping them on disk so each is only ever
+/// downloaded once.
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!("advent-tools/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Client { config, agent, last_request: None }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The day's puzzle input, as `NN.txt` in the input directory.
+    pub fn input(&mut self, day: u32) -> Result<Fetched, FetchError> {
+        let path = self.config.input_dir.join(format!("{:02}.txt", day));
+        self.cache(path, &format!("/{}/day/{}/input", self.config.year, day))
+    }
+
+    /// The day's puzzle page as served, as `NN.html` in the problem directory.
+    pub fn puzzle(&mut self, day: u32) -> Result<Fetched, FetchError> {
+        let path = self.config.puzzle_path(day);
+        self.cache(path, &format!("/{}/day/{}", self.config.year, day))
+    }
+
+    /// Downloads the day's puzzle page again over the one on disk. The page only shows
+    /// part 2 once part 1 is solved, so this is the one file worth fetching twice.
+    pub fn refresh_puzzle(&mut self, day: u32) -> Result<Fetched, FetchError> {
+        let path = self.config.puzzle_path(day);
+        self.download(path, &format!("/{}/day/{}", self.config.year, day))
+    }
+
+    fn cache(&mut self, path: PathBuf, url_path: &str) -> Result<Fetched, FetchError> {
+        if path.exists() {
+            return Ok(Fetched { path, cached: true });
+        }
+        self.download(path, url_path)
+    }
+
+    fn download(&mut self, path: PathBuf, url_path: &str) -> Result<Fetched, FetchError> {
+        let body = self.get(url_path)?;
+        write_atomic(&path, &body)?;
+
+        Ok(Fetched { path, cached: false })
+    }
+
+    fn get(&mut self, url_path: &str) -> Result<String, FetchError> {
+        let request = self.request("GET", url_path)?;
+        request.call()?.into_string().map_err(FetchError::Io)
+    }
+
+    pub(crate) fn post(&mut self, url_path: &str, form: &[(&str, &str)]) -> Result<String, FetchError> {
+        let request = self.request("POST", url_path)?;
+        request.send_form(form)?.into_string().map_err(FetchError::Io)
+    }
+
+    fn request(&mut self, method: &str, url_path: &str) -> Result<ureq::Request, FetchError> {
+        let session = self.config.session.clone().ok_or(FetchError::NoSession)?;
+        self.throttle();
+
+        Ok(self.agent.request(method, &format!("{}{}", self.config.base_url, url_path))
+            .set("Cookie", &format!("session={}", session)))
+    }
+
+    // Waits until the minimum interval has passed since the previous request started
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.config.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+// Writes to a file beside `path` and renames it into place, so a write that is cut short
+// never leaves a partial file behind to be taken as cached
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let part = path.with_file_name(format!(".{}.part", name));
+    fs::write(&part, contents)
+        .and_then(|_| fs::rename(&part, path))
+        .inspect_err(|_| { let _ = fs::remove_file(&part); })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{StubServer, config};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn site() -> StubServer {
+        StubServer::start(|r| match r.path.as_str() {
+            "/2025/day/1/input" => (200, "L68\nR48\n".to_owned()),
+            "/2025/day/1" => (200, "<article><h2>--- Day 1 ---</h2></article>".to_owned()),
+            "/2025/day/2/input" => (200, "11-22\n".to_owned()),
+            _ => (404, "Please don't repeatedly request this endpoint before it unlocks!".to_owned()),
+        })
+    }
+
+    #[test]
+    fn test_for_year() {
+        let config = Config::for_year(2017);
+
+        assert_eq!(config.year, 2017);
+        assert_eq!(config.input_dir, PathBuf::from("years/2017/input"));
+        assert_eq!(config.problem_dir, PathBuf::from("years/2017/problem"));
+        assert_eq!(config.answer_dir, PathBuf::from("years/2017/answer"));
+        assert_eq!(config.puzzle_path(7), PathBuf::from("years/2017/problem/07.html"));
+        assert_eq!(Config::default().year, 2025);
+
+        let project = advent::config::Config::from_toml("input_dir = \"in/{year}\"", Path::new("/repo")).unwrap();
+        let config = Config::load(&project, 2017);
+        assert_eq!(config.input_dir, PathBuf::from("/repo/in/2017"));
+        assert_eq!(config.answer_dir, PathBuf::from("/repo/years/2017/answer"));
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        let fetched = client.input(1).unwrap();
+        assert!(!fetched.cached);
+        assert!(fetched.path.ends_with("input/01.txt"));
+        assert_eq!(fs::read_to_string(&fetched.path).unwrap(), "L68\nR48\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2025/day/1/input");
+        assert_eq!(requests[0].headers["cookie"], "session=abc123");
+        assert!(requests[0].headers["user-agent"].starts_with("advent-tools/"));
+    }
+
+    #[test]
+    fn test_fetched_input_parses() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        // Saved as served, with LF endings and a final line break, as tests then read it
+        let fetched = client.input(1).unwrap();
+        let input = fs::read_to_string(&fetched.path).unwrap();
+        assert_eq!(advent::input_as_lines(&input), ["L68", "R48"]);
+        assert_eq!(advent::input_as_grid(&input).len(), 6);
+        assert_eq!(advent::input_as_lines(&input.replace('\n', "\r\n")), ["L68", "R48"]);
+    }
+
+    #[test]
+    fn test_fetch_puzzle() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        let fetched = client.puzzle(1).unwrap();
+        assert!(fetched.path.ends_with("problem/01.html"));
+        assert!(fs::read_to_string(&fetched.path).unwrap().contains("--- Day 1 ---"));
+    }
+
+    #[test]
+    fn test_never_refetches() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        let first = client.input(1).unwrap();
+        fs::write(&first.path, "edited by hand").unwrap();
+        let second = client.input(1).unwrap();
+
+        assert!(second.cached);
+        assert_eq!(second.path, first.path);
+        assert_eq!(fs::read_to_string(&second.path).unwrap(), "edited by hand");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_puzzle() {
+        let server = StubServer::start({
+            let served = AtomicUsize::new(0);
+            move |r| match (r.path.as_str(), served.fetch_add(1, Ordering::Relaxed)) {
+                ("/2025/day/1", 0) => (200, "<article><h2>--- Day 1 ---</h2></article>".to_owned()),
+                ("/2025/day/1", _) => (200, "<article><h2>--- Day 1 ---</h2></article><article><h2>--- Part Two ---</h2></article>".to_owned()),
+                _ => (404, String::new()),
+            }
+        });
+        let mut client = Client::new(config(&server));
+
+        let first = client.puzzle(1).unwrap();
+        assert!(client.puzzle(1).unwrap().cached);
+        assert!(!fs::read_to_string(&first.path).unwrap().contains("Part Two"));
+
+        let refreshed = client.refresh_puzzle(1).unwrap();
+        assert!(!refreshed.cached);
+        assert_eq!(refreshed.path, first.path);
+        assert!(fs::read_to_string(&refreshed.path).unwrap().contains("--- Part Two ---"));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_partial_write_is_not_cached() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        // Left behind by a fetch that was interrupted while writing
+        let part = client.config().input_dir.join(".01.txt.part");
+        fs::create_dir_all(part.parent().unwrap()).unwrap();
+        fs::write(&part, "L6").unwrap();
+
+        let fetched = client.input(1).unwrap();
+        assert!(!fetched.cached);
+        assert_eq!(fs::read_to_string(&fetched.path).unwrap(), "L68\nR48\n");
+        assert!(!part.exists());
+        assert_eq!(fs::read_dir(&client.config().input_dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_errors_leave_no_file() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        match client.input(25) {
+            Err(FetchError::Status(404, body)) => assert!(body.contains("before it unlocks")),
+            other => panic!("expected a 404, got {:?}", other),
+        }
+        assert!(!client.config().input_dir.join("25.txt").exists());
+
+        let mut anonymous = Client::new(Config { session: None, ..config(&server) });
+        assert!(matches!(anonymous.input(1), Err(FetchError::NoSession)));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let server = site();
+        let interval = Duration::from_millis(150);
+        let mut client = Client::new(Config { min_interval: interval, ..config(&server) });
+
+        let start = Instant::now();
+        client.input(1).unwrap();
+        client.input(1).unwrap();
+        assert!(start.elapsed() < interval, "cached reads should not wait");
+
+        client.input(2).unwrap();
+        client.puzzle(1).unwrap();
+        assert!(start.elapsed() >= interval * 2);
+        assert_eq!(server.requests().len(), 3);
+    }
+}
@@ -0,0 +1,311 @@
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A private leaderboard, as exported from the site's JSON link.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    /// When day 1 unlocked. Older exports leave this out.
+    pub day1_ts: Option<i64>,
+    pub num_days: Option<u32>,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub last_star_ts: i64,
+    /// Stars earned, by day and then part.
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// When the member earned a star, as a Unix timestamp.
+    pub fn star_ts(&self, day: u32, part: u8) -> Option<i64> {
+        self.completion_day_level.get(&day)?.get(&part).map(|s| s.get_star_ts)
+    }
+
+    /// Time taken from part 1 to part 2 of a day, in seconds.
+    pub fn delta(&self, day: u32) -> Option<i64> {
+        Some(self.star_ts(day, 2)? - self.star_ts(day, 1)?)
+    }
+
+    fn star_glyph(&self, day: u32) -> char {
+        match self.completion_day_level.get(&day).map_or(0, |parts| parts.len()) {
+            0 => '.',
+            1 => '+',
+            _ => '*',
+        }
+    }
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Leaderboard::from_json(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Number of days in the event: as exported, or else 25 before 2025 and 12 after.
+    pub fn days(&self) -> u32 {
+        self.num_days.unwrap_or(if self.year() < 2025 { 25 } else { 12 })
+    }
+
+    pub fn year(&self) -> i64 {
+        self.event.parse().unwrap_or(0)
+    }
+
+    /// When a day unlocked, as a Unix timestamp. Puzzles unlock at midnight US Eastern.
+    pub fn unlock_ts(&self, day: u32) -> i64 {
+        let day1 = self.day1_ts.unwrap_or_else(|| days_from_civil(self.year(), 12, 1) * 86_400 + 5 * 3_600);
+        day1 + (day as i64 - 1) * 86_400
+    }
+
+    /// Members by local score, ties going to whoever reached it first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+
+    /// Local scores worked out from the star times: for each star, the first member to get
+    /// it scores one point per member, the next one fewer, and so on.
+    pub fn computed_local_scores(&self) -> HashMap<u64, u64> {
+        let n = self.members.len() as u64;
+        let mut scores = self.members.values().map(|m| (m.id, 0)).collect::<HashMap<_, _>>();
+
+        for day in 1..=self.days() {
+            for part in 1..=2 {
+                let mut finishers = self.members.values()
+                    .filter_map(|m| m.completion_day_level.get(&day)?.get(&part).map(|s| (s.get_star_ts, s.star_index, m.id)))
+                    .collect::<Vec<_>>();
+                finishers.sort();
+
+                for (place, (_, _, id)) in finishers.into_iter().enumerate() {
+                    *scores.get_mut(&id).unwrap() += n - place as u64;
+                }
+            }
+        }
+
+        scores
+    }
+
+    /// Rank, score and a star per day for every member: `*` for both parts, `+` for
+    /// part 1 only and `.` for neither.
+    pub fn render_ranking(&self) -> String {
+        let days = self.days();
+        let mut out = String::new();
+
+        let header = (1..=days).map(|d| if d % 10 == 0 { char::from(b'0' + (d / 10) as u8) } else { ' ' }).collect::<String>();
+        let units = (1..=days).map(|d| char::from(b'0' + (d % 10) as u8)).collect::<String>();
+        let _ = writeln!(out, "{:12}{}", "", header.trim_end());
+        let _ = writeln!(out, "{:12}{}", "", units);
+
+        let ranking = self.ranking();
+        for (i, m) in ranking.iter().enumerate() {
+            let tied = i > 0 && ranking[i - 1].local_score == m.local_score;
+            let rank = if tied { String::new() } else { format!("{})", i + 1) };
+            let stars = (1..=days).map(|d| m.star_glyph(d)).collect::<String>();
+            let _ = writeln!(out, "{:>4} {:>5}  {}  {}", rank, m.local_score, stars, m.display_name());
+        }
+
+        out
+    }
+
+    /// For every member, when each star came after its puzzle unlocked.
+    pub fn render_timelines(&self) -> String {
+        let mut out = String::new();
+
+        for m in self.ranking() {
+            let _ = writeln!(out, "{} ({} stars)", m.display_name(), m.stars);
+            if m.completion_day_level.is_empty() {
+                let _ = writeln!(out, "  no stars yet");
+            }
+
+            for &day in m.completion_day_level.keys() {
+                let since_unlock = |part| m.star_ts(day, part).map(|ts| ts - self.unlock_ts(day));
+                let _ = writeln!(
+                    out, "  day {:>2}  {:>10}  {:>10}",
+                    day, format_duration(since_unlock(1)), format_duration(since_unlock(2)),
+                );
+            }
+        }
+
+        out
+    }
+
+    /// For every day someone has started, how long each member took from part 1 to part 2,
+    /// quickest first.
+    pub fn render_deltas(&self) -> String {
+        let mut out = String::new();
+
+        for day in 1..=self.days() {
+            let mut started = self.members.values()
+                .filter(|m| m.star_ts(day, 1).is_some())
+                .collect::<Vec<_>>();
+            if started.is_empty() {
+                continue;
+            }
+
+            // Members still on part 1 go last
+            started.sort_by_key(|m| (m.delta(day).is_none(), m.delta(day), m.id));
+
+            let _ = writeln!(out, "Day {}", day);
+            for m in started {
+                let _ = writeln!(out, "  {:>10}  {}", format_duration(m.delta(day)), m.display_name());
+            }
+        }
+
+        out
+    }
+}
+
+// Renders seconds as h:mm:ss, or a dash when there is nothing to time
+fn format_duration(secs: Option<i64>) -> String {
+    match secs {
+        Some(s) => format!("{}:{:02}:{:02}", s / 3_600, s / 60 % 60, s % 60),
+        None => "-".to_owned(),
+    }
+}
+
+// Days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_2025() -> Leaderboard {
+        Leaderboard::from_json(include_str!("../fixtures/leaderboard_2025.json")).unwrap()
+    }
+
+    fn board_2023() -> Leaderboard {
+        Leaderboard::from_json(include_str!("../fixtures/leaderboard_2023.json")).unwrap()
+    }
+
+    #[test]
+    fn test_from_json() {
+        let board = board_2025();
+
+        assert_eq!(board.members.len(), 4);
+        assert_eq!(board.days(), 12);
+        let alice = &board.members["101"];
+        assert_eq!(alice.display_name(), "Alice");
+        assert_eq!(alice.star_ts(1, 2), Some(board.unlock_ts(1) + 580));
+        assert_eq!(alice.star_ts(3, 2), None);
+        assert_eq!(alice.delta(1), Some(268));
+        assert_eq!(board.members["303"].display_name(), "(anonymous user #303)");
+
+        assert!(Leaderboard::from_json("{\"event\": \"2025\"}").is_err());
+    }
+
+    #[test]
+    fn test_unlock_ts() {
+        // 2025-12-01T05:00:00Z, as exported and as worked out from the year
+        assert_eq!(board_2025().unlock_ts(1), 1_764_565_200);
+        assert_eq!(Leaderboard { day1_ts: None, ..board_2025() }.unlock_ts(1), 1_764_565_200);
+
+        let board = board_2023();
+        assert_eq!(board.days(), 25);
+        assert_eq!(board.unlock_ts(1), 1_701_406_800);
+        assert_eq!(board.unlock_ts(25), 1_701_406_800 + 24 * 86_400);
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+    }
+
+    #[test]
+    fn test_local_scores() {
+        for board in [board_2025(), board_2023()] {
+            let computed = board.computed_local_scores();
+            for m in board.members.values() {
+                assert_eq!(computed[&m.id], m.local_score, "{}", m.display_name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_ranking() {
+        let expected = [
+            "                     1",
+            "            123456789012",
+            "  1)    18  **+.........  Alice",
+            "  2)    14  **..........  Bob",
+            "  3)     4  *...........  (anonymous user #303)",
+            "  4)     0  ............  Carol",
+        ];
+        assert_eq!(board_2025().render_ranking().lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_render_ranking_ties() {
+        let mut board = board_2023();
+        board.members.get_mut("8").unwrap().local_score = 7;
+
+        let rows = board.render_ranking().lines().skip(2).map(str::to_owned).collect::<Vec<_>>();
+        assert!(rows[0].starts_with("  1)     7  *"));
+        assert!(rows[1].starts_with("         7  *"));
+        assert!(rows[0].ends_with("Eve"), "Eve reached 7 first");
+    }
+
+    #[test]
+    fn test_render_timelines() {
+        let expected = "\
+Alice (5 stars)
+  day  1     0:05:12     0:09:40
+  day  2     0:25:00     0:31:40
+  day  3     0:11:40           -
+Bob (4 stars)
+  day  1     0:04:10     1:06:10
+  day  2     0:30:00     0:30:50
+(anonymous user #303) (2 stars)
+  day  1    25:00:00    25:01:00
+Carol (0 stars)
+  no stars yet
+";
+        assert_eq!(board_2025().render_timelines(), expected);
+    }
+
+    #[test]
+    fn test_render_deltas() {
+        let expected = "\
+Day 1
+     0:01:00  (anonymous user #303)
+     0:04:28  Alice
+     1:02:00  Bob
+Day 2
+     0:00:50  Bob
+     0:06:40  Alice
+Day 3
+           -  Alice
+";
+        assert_eq!(board_2025().render_deltas(), expected);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod fetch;
+pub mod leaderboard;
+pub mod puzzle;
+pub mod submit;
+
+#[cfg(test)]
+mod stub;
@@ -0,0 +1,186 @@
+use advent::config::Config as ProjectConfig;
+use advent::run;
+use advent_tools::fetch::{Client, Config};
+use advent_tools::leaderboard::Leaderboard;
+use advent_tools::puzzle::convert_file;
+use advent_tools::submit::Outcome;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: aoc [--year <year>] fetch [--refresh] <day>...
+       aoc [--year <year>] convert <day>...
+       aoc [--year <year>] submit <day> <part> <answer>
+       aoc [--year <year>] inputs [<day>...]
+       aoc leaderboard <file.json> [ranking|timelines|deltas]
+
+The year and directories default to aoc.toml, and --input-dir, --problem-dir and
+--answer-dir override it.
+
+Files already on disk are not fetched again, except that fetch --refresh downloads
+the puzzle pages anew to pick up part 2. A correct part 1 submission does the same.";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let mut project = match ProjectConfig::load() {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let args = match project.apply_args(&args) {
+        Ok(rest) => rest,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = Config::load(&project, project.year);
+
+    match args.split_first() {
+        Some((command, [flag, days @ ..])) if command == "fetch" && flag == "--refresh" && !days.is_empty() => fetch(config, days, true),
+        Some((command, days)) if command == "fetch" && !days.is_empty() => fetch(config, days, false),
+        Some((command, days)) if command == "convert" && !days.is_empty() => convert(&config, days),
+        Some((command, [day, part, answer])) if command == "submit" => submit(config, day, part, answer),
+        Some((command, days)) if command == "inputs" => inputs(&project, days),
+        Some((command, [file, views @ ..])) if command == "leaderboard" => leaderboard(file, views),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(config: Config, days: &[String], refresh: bool) -> ExitCode {
+    let Some(days) = parse_days(days) else {
+        return ExitCode::FAILURE;
+    };
+    let mut client = Client::new(config);
+
+    for &day in &days {
+        let input = client.input(day);
+        let puzzle = if refresh { client.refresh_puzzle(day) } else { client.puzzle(day) };
+        for result in [input, puzzle] {
+            match result {
+                Ok(f) if f.cached => println!("{} (cached)", f.path.display()),
+                Ok(f) => println!("{}", f.path.display()),
+                Err(e) => {
+                    eprintln!("day {}: {}", day, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    convert_days(client.config(), &days)
+}
+
+fn convert(config: &Config, days: &[String]) -> ExitCode {
+    match parse_days(days) {
+        Some(days) => convert_days(config, &days),
+        None => ExitCode::FAILURE,
+    }
+}
+
+fn convert_days(config: &Config, days: &[u32]) -> ExitCode {
+    for &day in days {
+        let html = config.puzzle_path(day);
+        match convert_file(&html) {
+            Ok(path) => println!("{}", path.display()),
+            Err(e) => {
+                eprintln!("{}: {}", html.display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn submit(config: Config, day: &str, part: &str, answer: &str) -> ExitCode {
+    let (Ok(day), Ok(part @ 1..=2)) = (day.parse::<u32>(), part.parse::<u8>()) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    let mut client = Client::new(config);
+    match client.submit(day, part, answer) {
+        Ok(Outcome::Correct) => {
+            println!("correct");
+            // The page now has part 2 to read
+            if part == 1 {
+                match client.refresh_puzzle(day) {
+                    Ok(_) => return convert_days(client.config(), &[day]),
+                    Err(e) => eprintln!("day {}: unable to refresh the puzzle page: {}", day, e),
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(Outcome::Unrecognised(text)) => {
+            println!("unrecognised response: {}", text);
+            ExitCode::FAILURE
+        }
+        Ok(outcome) => {
+            println!("{}", outcome);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("day {} part {}: {}", day, part, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn inputs(project: &ProjectConfig, days: &[String]) -> ExitCode {
+    if days.is_empty() {
+        return run::list(project, project.year, None);
+    }
+
+    let Some(days) = parse_days(days) else {
+        return ExitCode::FAILURE;
+    };
+    for day in days {
+        if run::list(project, project.year, Some(day)) == ExitCode::FAILURE {
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+// Day numbers from the command line, reporting the first argument that is not one
+fn parse_days(days: &[String]) -> Option<Vec<u32>> {
+    days.iter()
+        .map(|day| day.parse().inspect_err(|_| eprintln!("not a day: {}", day)).ok())
+        .collect()
+}
+
+fn leaderboard(file: &str, views: &[String]) -> ExitCode {
+    let board = match Leaderboard::load(Path::new(file)) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("{}: {}", file, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let all = ["ranking", "timelines", "deltas"].map(String::from);
+    let views = if views.is_empty() { &all[..] } else { views };
+
+    for view in views {
+        let text = match view.as_str() {
+            "ranking" => board.render_ranking(),
+            "timelines" => board.render_timelines(),
+            "deltas" => board.render_deltas(),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+        println!("{}", text);
+    }
+
+    ExitCode::SUCCESS
+}
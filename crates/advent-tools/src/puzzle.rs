@@ -0,0 +1,422 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A run of text within a paragraph or list item.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub code: bool,
+    /// Highlighted with `<em>`, which is how the puzzle marks the numbers that matter.
+    pub em: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Block {
+    Paragraph(Vec<Span>),
+    /// The text of a `<pre><code>` example, highlighting dropped.
+    Code(String),
+    List(Vec<Vec<Span>>),
+}
+
+/// One `<article>` of the puzzle page, which holds a single part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Part {
+    pub heading: String,
+    pub blocks: Vec<Block>,
+}
+
+/// The puzzle description from a downloaded page, with everything outside its articles
+/// left behind.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Document {
+    pub parts: Vec<Part>,
+}
+
+impl Document {
+    pub fn from_html(html: &str) -> Self {
+        let mut builder = Builder::default();
+        for token in tokenize(html) {
+            builder.push(token);
+        }
+        Document { parts: builder.parts }
+    }
+
+    /// The text of every example block in a part, counting parts from 1.
+    pub fn examples(&self, part: usize) -> Vec<&str> {
+        self.blocks(part)
+            .filter_map(|b| match b {
+                Block::Code(code) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The highlighted text in a part's paragraphs and lists, counting parts from 1.
+    pub fn highlights(&self, part: usize) -> Vec<&str> {
+        self.blocks(part)
+            .flat_map(|b| match b {
+                Block::Paragraph(spans) => spans.iter().collect::<Vec<_>>(),
+                Block::List(items) => items.iter().flatten().collect(),
+                Block::Code(_) => Vec::new(),
+            })
+            .filter(|s| s.em)
+            .map(|s| s.text.as_str())
+            .collect()
+    }
+
+    fn blocks(&self, part: usize) -> impl Iterator<Item = &Block> {
+        self.parts.get(part.wrapping_sub(1)).into_iter().flat_map(|p| &p.blocks)
+    }
+
+    /// The document as Markdown: a heading per part, examples fenced, code in backticks
+    /// and highlights in bold. `advent::puzzle` reads this form back.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        for part in &self.parts {
+            let _ = writeln!(md, "## {}\n", part.heading);
+            for block in &part.blocks {
+                match block {
+                    Block::Paragraph(spans) => { let _ = writeln!(md, "{}\n", inline_markdown(spans)); }
+                    Block::Code(code) => { let _ = writeln!(md, "```\n{}\n```\n", code.trim_end_matches('\n')); }
+                    Block::List(items) => {
+                        items.iter().for_each(|item| { let _ = writeln!(md, "- {}", inline_markdown(item)); });
+                        md.push('\n');
+                    }
+                }
+            }
+        }
+
+        md.truncate(md.trim_end().len());
+        md.push('\n');
+        md
+    }
+}
+
+/// Converts a downloaded puzzle page to Markdown in a file beside it, returning the new
+/// file's path.
+pub fn convert_file(html: &Path) -> io::Result<PathBuf> {
+    let doc = Document::from_html(&fs::read_to_string(html)?);
+    let path = html.with_extension("md");
+    fs::write(&path, doc.to_markdown())?;
+    Ok(path)
+}
+
+fn inline_markdown(spans: &[Span]) -> String {
+    spans.iter()
+        .map(|s| {
+            let text = if s.code { format!("`{}`", s.text) } else { s.text.clone() };
+            if s.em { format!("**{}**", text) } else { text }
+        })
+        .collect()
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Token<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    Text(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let end = tag.find('>').unwrap_or(tag.len());
+            let (closing, inner) = match tag[..end].strip_prefix('/') {
+                Some(inner) => (true, inner),
+                None => (false, &tag[..end]),
+            };
+
+            let name = inner.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+            if !name.starts_with('!') {
+                tokens.push(if closing { Token::Close(name) } else { Token::Open(name) });
+            }
+            rest = tag.get(end + 1..).unwrap_or("");
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                e => match e.strip_prefix("#x").or_else(|| e.strip_prefix("#X")) {
+                    Some(hex) => char::from_u32(u32::from_str_radix(hex, 16).ok()?)?,
+                    None => char::from_u32(e.strip_prefix('#')?.parse().ok()?)?,
+                },
+            };
+            Some((c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => { out.push(c); rest = &rest[len..]; }
+            None => { out.push('&'); rest = &rest[1..]; }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// Walks the tokens, keeping only what sits inside an <article>
+#[derive(Default)]
+struct Builder {
+    parts: Vec<Part>,
+    in_article: bool,
+    in_heading: bool,
+    in_pre: bool,
+    code: usize,
+    em: usize,
+    spans: Vec<Span>,
+    items: Vec<Vec<Span>>,
+    text: String,
+}
+
+impl Builder {
+    fn push(&mut self, token: Token) {
+        match token {
+            Token::Open("article") => {
+                self.in_article = true;
+                self.parts.push(Part { heading: String::new(), blocks: Vec::new() });
+            }
+            Token::Close("article") => {
+                self.end_paragraph();
+                self.in_article = false;
+            }
+            _ if !self.in_article => {}
+
+            Token::Open("h2") => self.in_heading = true,
+            Token::Close("h2") => self.in_heading = false,
+            Token::Open("pre") => { self.end_paragraph(); self.in_pre = true; }
+            Token::Close("pre") => {
+                self.in_pre = false;
+                let code = std::mem::take(&mut self.text);
+                self.block(Block::Code(code));
+            }
+            Token::Open("p") => self.end_paragraph(),
+            Token::Close("p") => self.end_paragraph(),
+            Token::Open("ul") => self.end_paragraph(),
+            Token::Open("li") => self.spans.clear(),
+            Token::Close("li") => {
+                let item = trim_spans(std::mem::take(&mut self.spans));
+                self.items.push(item);
+            }
+            Token::Close("ul") => {
+                let items = std::mem::take(&mut self.items);
+                self.block(Block::List(items));
+            }
+            Token::Open("code") => self.code += 1,
+            Token::Close("code") => self.code = self.code.saturating_sub(1),
+            Token::Open("em") => self.em += 1,
+            Token::Close("em") => self.em = self.em.saturating_sub(1),
+            Token::Open(_) | Token::Close(_) => {}
+
+            Token::Text(text) => {
+                if self.in_heading {
+                    let part = self.parts.last_mut().unwrap();
+                    part.heading.push_str(text.trim());
+                } else if self.in_pre {
+                    self.text.push_str(&text);
+                } else {
+                    self.span(text);
+                }
+            }
+        }
+    }
+
+    fn span(&mut self, text: String) {
+        let (code, em) = (self.code > 0, self.em > 0);
+        let text = if code { text } else { collapse_whitespace(&text) };
+
+        match self.spans.last_mut() {
+            Some(last) if last.code == code && last.em == em => last.text.push_str(&text),
+            _ if text.is_empty() => {}
+            _ => self.spans.push(Span { text, code, em }),
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        let spans = trim_spans(std::mem::take(&mut self.spans));
+        if !spans.is_empty() {
+            self.block(Block::Paragraph(spans));
+        }
+    }
+
+    fn block(&mut self, block: Block) {
+        if let Some(part) = self.parts.last_mut() {
+            part.blocks.push(block);
+        }
+    }
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut out = String::new();
+    for (i, word) in s.split_whitespace().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    if s.starts_with(char::is_whitespace) && !out.is_empty() {
+        out.insert(0, ' ');
+    }
+    if s.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out
+}
+
+// Drops whitespace at either end of a run of spans, and any spans left empty
+fn trim_spans(mut spans: Vec<Span>) -> Vec<Span> {
+    if let Some(first) = spans.first_mut().filter(|s| !s.code) {
+        first.text = first.text.trim_start().to_owned();
+    }
+    if let Some(last) = spans.last_mut().filter(|s| !s.code) {
+        last.text = last.text.trim_end().to_owned();
+    }
+    spans.retain(|s| !s.text.is_empty());
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 1 - Advent of Code 2025</title></head>
+<body><header><h1><a href="/">Advent of Code</a></h1></header>
+<main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>The dial starts by pointing at <code>50</code>.
+You follow the rotations &amp; count.</p>
+<p>For example:</p>
+<pre><code>L68
+L30
+R48
+</code></pre>
+<ul>
+<li>The dial is rotated <code>L68</code> to point at <code>82</code>.</li>
+<li>It ends at <em>0</em>.</li>
+</ul>
+<p>Because the dial points at <code>0</code> a total of three times, the password in this example is <code><em>3</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1150</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Count <em>every</em> click &lt;through&gt; <code>0</code>:</p>
+<pre><code>R1000
+</code></pre>
+<p>In this example, the new password would be <code><em>6</em></code>.</p>
+</article>
+</main></body></html>
+"#;
+
+    fn span(text: &str, code: bool, em: bool) -> Span {
+        Span { text: text.to_owned(), code, em }
+    }
+
+    #[test]
+    fn test_from_html() {
+        let doc = Document::from_html(PAGE);
+
+        assert_eq!(doc.parts.len(), 2);
+        assert_eq!(doc.parts[0].heading, "--- Day 1: Secret Entrance ---");
+        assert_eq!(doc.parts[1].heading, "--- Part Two ---");
+
+        assert_eq!(doc.parts[0].blocks[0], Block::Paragraph(vec![
+            span("The dial starts by pointing at ", false, false),
+            span("50", true, false),
+            span(". You follow the rotations & count.", false, false),
+        ]));
+        assert_eq!(doc.parts[0].blocks[1], Block::Paragraph(vec![span("For example:", false, false)]));
+        assert_eq!(doc.parts[0].blocks[2], Block::Code("L68\nL30\nR48\n".to_owned()));
+        assert_eq!(doc.parts[0].blocks[3], Block::List(vec![
+            vec![span("The dial is rotated ", false, false), span("L68", true, false), span(" to point at ", false, false), span("82", true, false), span(".", false, false)],
+            vec![span("It ends at ", false, false), span("0", false, true), span(".", false, false)],
+        ]));
+        assert_eq!(doc.parts[0].blocks.len(), 5);
+    }
+
+    #[test]
+    fn test_examples_and_highlights() {
+        let doc = Document::from_html(PAGE);
+
+        assert_eq!(doc.examples(1), vec!["L68\nL30\nR48\n"]);
+        assert_eq!(doc.examples(2), vec!["R1000\n"]);
+        assert_eq!(doc.highlights(1), vec!["0", "3"]);
+        assert_eq!(doc.highlights(2), vec!["every", "6"]);
+        assert!(doc.examples(3).is_empty());
+        assert!(doc.examples(0).is_empty());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let md = Document::from_html(PAGE).to_markdown();
+        let expected = "\
+## --- Day 1: Secret Entrance ---
+
+The dial starts by pointing at `50`. You follow the rotations & count.
+
+For example:
+
+```
+L68
+L30
+R48
+```
+
+- The dial is rotated `L68` to point at `82`.
+- It ends at **0**.
+
+Because the dial points at `0` a total of three times, the password in this example is **`3`**.
+
+## --- Part Two ---
+
+Count **every** click <through> `0`:
+
+```
+R1000
+```
+
+In this example, the new password would be **`6`**.
+";
+        assert_eq!(md, expected);
+    }
+
+    #[test]
+    fn test_markdown_reads_back() {
+        let doc = Document::from_html(PAGE);
+        let md = doc.to_markdown();
+        let read = advent::puzzle::Description::from(md.as_str());
+
+        for part in 1..=2 {
+            assert_eq!(read.examples(part), doc.examples(part));
+            assert_eq!(read.highlights(part), doc.highlights(part));
+        }
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp;&amp; &#39;c&#x27; &bogus; &"), "a <b> && 'c' &bogus; &");
+    }
+}
@@ -0,0 +1,419 @@
+use crate::fetch::{Client, FetchError};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint as to which way.
+    Wrong,
+    /// Submitted too soon after the last attempt, so not checked.
+    Wait(Duration),
+    /// The part is already solved, or not yet unlocked.
+    WrongLevel,
+    Unrecognised(String),
+}
+
+impl Outcome {
+    /// Reads the outcome from the page the site returns after a submission.
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+        let lower = text.to_lowercase();
+
+        if lower.contains("that's the right answer") {
+            Outcome::Correct
+        } else if lower.contains("that's not the right answer") {
+            if lower.contains("too high") {
+                Outcome::TooHigh
+            } else if lower.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if let Some(wait) = parse_wait(&lower) {
+            Outcome::Wait(wait)
+        } else if lower.contains("solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unrecognised(text)
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(d) => write!(f, "wait:{}", d.as_secs()),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+            Outcome::Unrecognised(_) => write!(f, "unrecognised"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "wrong-level" => Outcome::WrongLevel,
+            "unrecognised" => Outcome::Unrecognised(String::new()),
+            _ => match s.strip_prefix("wait:").and_then(|n| n.parse().ok()) {
+                Some(secs) => Outcome::Wait(Duration::from_secs(secs)),
+                None => return Err(format!("unknown outcome '{}'", s)),
+            },
+        })
+    }
+}
+
+// The text of the page's <article>, with tags dropped and whitespace collapsed
+fn article_text(page: &str) -> String {
+    let start = page.find("<article").unwrap_or(0);
+    let end = page[start..].find("</article>").map_or(page.len(), |e| start + e);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => { in_tag = false; text.push(' ') }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Reads "you have 1m 12s left to wait"
+fn parse_wait(lower: &str) -> Option<Duration> {
+    let before = &lower[..lower.find("left to wait")?];
+    let amount = &before[before.rfind("you have ")? + "you have ".len()..];
+
+    let mut secs = 0;
+    for token in amount.split_whitespace() {
+        let (n, unit) = token.split_at(token.len() - 1);
+        let n = n.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    Blank,
+    /// The part was already solved with this answer.
+    Solved(String),
+    /// This exact answer was already rejected.
+    KnownWrong(Outcome),
+    /// An answer no greater than this one was too high.
+    TooHigh(String),
+    /// An answer no smaller than this one was too low.
+    TooLow(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Blank => write!(f, "the answer is blank"),
+            Refusal::Solved(answer) => write!(f, "already solved, the answer was {}", answer),
+            Refusal::KnownWrong(outcome) => write!(f, "already submitted and {}", outcome),
+            Refusal::TooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::TooLow(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+/// Every answer submitted so far, one per line of a tab separated file.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Reads the history at `path`, which is empty if the file does not exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let attempts = text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                parse_attempt(line).ok_or_else(|| io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: unreadable attempt '{}'", path.display(), i + 1, line),
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(History { path: path.to_owned(), attempts })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Whether `answer` is worth sending, given every earlier attempt at the same part.
+    pub fn check(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<(), Refusal> {
+        if answer.is_empty() {
+            return Err(Refusal::Blank);
+        }
+
+        let earlier = self.attempts.iter().filter(|a| a.year == year && a.day == day && a.part == part);
+        for attempt in earlier {
+            if attempt.outcome == Outcome::Correct {
+                return Err(Refusal::Solved(attempt.answer.clone()));
+            }
+            if attempt.outcome.is_wrong() && attempt.answer == answer {
+                return Err(Refusal::KnownWrong(attempt.outcome.clone()));
+            }
+
+            match (&attempt.outcome, compare_numbers(answer, &attempt.answer)) {
+                (Outcome::TooHigh, Some(Ordering::Greater)) => return Err(Refusal::TooHigh(attempt.answer.clone())),
+                (Outcome::TooLow, Some(Ordering::Less)) => return Err(Refusal::TooLow(attempt.answer.clone())),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the attempt to the end of the file.
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}\t{}\t{}\t{}\t{}", attempt.year, attempt.day, attempt.part, attempt.answer, attempt.outcome)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+fn parse_attempt(line: &str) -> Option<Attempt> {
+    let fields = line.split('\t').collect::<Vec<_>>();
+    let [year, day, part, answer, outcome] = fields[..] else { return None };
+
+    Some(Attempt {
+        year: year.parse().ok()?,
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        answer: answer.to_owned(),
+        outcome: outcome.parse().ok()?,
+    })
+}
+
+fn compare_numbers(a: &str, b: &str) -> Option<Ordering> {
+    Some(a.parse::<i128>().ok()?.cmp(&b.parse::<i128>().ok()?))
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Fetch(FetchError),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(r) => write!(f, "not submitted: {}", r),
+            SubmitError::Fetch(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<FetchError> for SubmitError {
+    fn from(e: FetchError) -> Self {
+        SubmitError::Fetch(e)
+    }
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(e: io::Error) -> Self {
+        SubmitError::Fetch(FetchError::Io(e))
+    }
+}
+
+impl Client {
+    /// The history file kept in the answer directory.
+    pub fn history_path(&self) -> PathBuf {
+        self.config().answer_dir.join("history.txt")
+    }
+
+    /// Sends an answer for one part of a day, unless the history shows it cannot be right,
+    /// and records what the site said about it.
+    pub fn submit(&mut self, day: u32, part: u8, answer: &str) -> Result<Outcome, SubmitError> {
+        let year = self.config().year;
+        let answer = answer.trim();
+        let mut history = History::load(&self.history_path())?;
+        history.check(year, day, part, answer).map_err(SubmitError::Refused)?;
+
+        let page = self.post(
+            &format!("/{}/day/{}/answer", year, day),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+
+        let outcome = Outcome::parse(&page);
+        history.record(Attempt { year, day, part, answer: answer.to_owned(), outcome: outcome.clone() })?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{StubServer, config};
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", message)
+    }
+
+    // Accepts 1150 for part 1, and says whether anything else is too high or too low
+    fn site() -> StubServer {
+        StubServer::start(|r| {
+            let form = r.body.split('&').filter_map(|kv| kv.split_once('=')).collect::<Vec<_>>();
+            let answer = form.iter().find(|(k, _)| *k == "answer").map_or(0, |(_, v)| v.parse::<i64>().unwrap_or(0));
+
+            let message = match answer.cmp(&1150) {
+                Ordering::Equal => "That's the right answer! You are <em>one gold star</em> closer.".to_owned(),
+                Ordering::Greater => "That's not the right answer; your answer is too high.  Please wait one minute.".to_owned(),
+                Ordering::Less => "That's not the right answer; your answer is too low.".to_owned(),
+            };
+            (200, page(&message))
+        })
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(Outcome::parse(&page("That's the right answer!")), Outcome::Correct);
+        assert_eq!(Outcome::parse(&page("That's not the right answer; your answer is too high.")), Outcome::TooHigh);
+        assert_eq!(Outcome::parse(&page("That's not the right answer; your answer is too low.")), Outcome::TooLow);
+        assert_eq!(Outcome::parse(&page("That's not the right answer.  If you're stuck...")), Outcome::Wrong);
+        assert_eq!(
+            Outcome::parse(&page("You gave an answer too recently; you have to wait.  You have 41s left to wait.")),
+            Outcome::Wait(Duration::from_secs(41)),
+        );
+        assert_eq!(
+            Outcome::parse(&page("You gave an answer too recently.  You have 1m 12s left to wait.")),
+            Outcome::Wait(Duration::from_secs(72)),
+        );
+        assert_eq!(
+            Outcome::parse(&page("You don't seem to be solving the right level.  Did you already complete it?")),
+            Outcome::WrongLevel,
+        );
+        assert_eq!(Outcome::parse("<p>Something   <b>else</b></p>"), Outcome::Unrecognised("Something else".to_owned()));
+    }
+
+    #[test]
+    fn test_outcome_round_trip() {
+        for outcome in [Outcome::Correct, Outcome::TooHigh, Outcome::TooLow, Outcome::Wrong, Outcome::WrongLevel, Outcome::Wait(Duration::from_secs(60))] {
+            assert_eq!(outcome.to_string().parse::<Outcome>(), Ok(outcome));
+        }
+        assert!("maybe".parse::<Outcome>().is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        assert_eq!(client.submit(1, 1, "2000").unwrap(), Outcome::TooHigh);
+        assert_eq!(client.submit(1, 1, " 900\n").unwrap(), Outcome::TooLow);
+        assert_eq!(client.submit(1, 1, "1150").unwrap(), Outcome::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2025/day/1/answer");
+        assert_eq!(requests[0].body, "level=1&answer=2000");
+        assert_eq!(requests[1].body, "level=1&answer=900");
+
+        let history = fs::read_to_string(client.history_path()).unwrap();
+        assert_eq!(history, "2025\t1\t1\t2000\ttoo-high\n2025\t1\t1\t900\ttoo-low\n2025\t1\t1\t1150\tcorrect\n");
+    }
+
+    #[test]
+    fn test_refuses_known_wrong() {
+        let server = site();
+        let mut client = Client::new(config(&server));
+
+        client.submit(1, 1, "2000").unwrap();
+        client.submit(1, 1, "900").unwrap();
+
+        let refusal = |r: Result<Outcome, SubmitError>| match r {
+            Err(SubmitError::Refused(refusal)) => refusal,
+            other => panic!("expected a refusal, got {:?}", other),
+        };
+        assert_eq!(refusal(client.submit(1, 1, "2000")), Refusal::KnownWrong(Outcome::TooHigh));
+        assert_eq!(refusal(client.submit(1, 1, "2001")), Refusal::TooHigh("2000".to_owned()));
+        assert_eq!(refusal(client.submit(1, 1, "899")), Refusal::TooLow("900".to_owned()));
+        assert_eq!(refusal(client.submit(1, 1, "")), Refusal::Blank);
+        assert_eq!(server.requests().len(), 2);
+
+        // Part 2 and other days have bounds of their own
+        assert_eq!(client.submit(1, 2, "5000").unwrap(), Outcome::TooHigh);
+        assert_eq!(client.submit(2, 1, "1").unwrap(), Outcome::TooLow);
+
+        assert_eq!(client.submit(1, 1, "1150").unwrap(), Outcome::Correct);
+        assert_eq!(refusal(client.submit(1, 1, "1150")), Refusal::Solved("1150".to_owned()));
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[test]
+    fn test_history_persists() {
+        let server = site();
+        let config = config(&server);
+        Client::new(config.clone()).submit(3, 1, "5000").unwrap();
+
+        let history = History::load(&Client::new(config.clone()).history_path()).unwrap();
+        assert_eq!(history.attempts(), &[Attempt { year: 2025, day: 3, part: 1, answer: "5000".to_owned(), outcome: Outcome::TooHigh }]);
+        assert_eq!(history.check(2025, 3, 1, "6000"), Err(Refusal::TooHigh("5000".to_owned())));
+        assert_eq!(history.check(2024, 3, 1, "6000"), Ok(()));
+
+        fs::write(Client::new(config).history_path(), "2025\t3\tnot a part\n").unwrap();
+        assert_eq!(History::load(&history.path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::Neighbourhood;
+
+/// A grid of booleans packed 64 to a word, each row starting on a fresh word.
+///
+/// Bits past the right edge of a row are always clear, so whole-word operations never
+/// need to mask them out.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+        BitGrid { width, height, stride, words: vec![0; stride * height] }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> bool) -> Self {
+        let mut grid = BitGrid::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                grid.set(x, y, f(x, y));
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    /// Whether the cell is set. Cells off the grid are never set.
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return false;
+        }
+        let (x, y) = (x as usize, y as usize);
+        self.words[y * self.stride + x / 64] >> (x % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(x < self.width && y < self.height, "({}, {}) is off the grid", x, y);
+        let word = &mut self.words[y * self.stride + x / 64];
+        let bit = 1 << (x % 64);
+        if value { *word |= bit } else { *word &= !bit }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&w| w != 0)
+    }
+
+    /// Coordinates of every set cell, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &w)| {
+            let (y, base) = (i / self.stride, (i % self.stride) * 64);
+            BitIter(w).map(move |b| (base + b, y))
+        })
+    }
+
+    pub fn and(&self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a & b)
+    }
+
+    pub fn or(&self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a | b)
+    }
+
+    pub fn and_not(&self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a & !b)
+    }
+
+    /// Cells with fewer than `threshold` set neighbours, whether or not they are set themselves.
+    ///
+    /// Each row's neighbour counts are summed word by word into four bit planes, so a
+    /// whole word of cells is counted with a few dozen bitwise operations.
+    pub fn neighbours_fewer_than(&self, threshold: usize, neighbourhood: Neighbourhood) -> BitGrid {
+        let mut result = BitGrid::new(self.width, self.height);
+        let empty = vec![0; self.stride];
+
+        for y in 0..self.height {
+            let above = if y > 0 { self.row(y - 1) } else { &empty };
+            let below = if y + 1 < self.height { self.row(y + 1) } else { &empty };
+            let row = self.row(y);
+
+            for i in 0..self.stride {
+                let inputs: &[u64] = match neighbourhood {
+                    Neighbourhood::Orthogonal => &[above[i], below[i], west(row, i), east(row, i)],
+                    Neighbourhood::Surrounding => &[
+                        west(above, i), above[i], east(above, i),
+                        west(row, i), east(row, i),
+                        west(below, i), below[i], east(below, i),
+                    ],
+                };
+
+                let mut planes = [0u64; 4];
+                for &input in inputs {
+                    let mut carry = input;
+                    for plane in planes.iter_mut() {
+                        let next = *plane & carry;
+                        *plane ^= carry;
+                        carry = next;
+                    }
+                }
+
+                result.words[y * self.stride + i] = fewer_than(&planes, threshold) & self.word_mask(i);
+            }
+        }
+
+        result
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    // The bits of word `i` in a row that fall inside the grid
+    fn word_mask(&self, i: usize) -> u64 {
+        match (i + 1 == self.stride, self.width % 64) {
+            (true, r) if r > 0 => (1 << r) - 1,
+            _ => !0,
+        }
+    }
+
+    fn zip(&self, other: &BitGrid, op: impl Fn(u64, u64) -> u64) -> BitGrid {
+        assert!(self.width == other.width && self.height == other.height, "grids differ in size");
+        let words = self.words.iter().zip(&other.words).map(|(&a, &b)| op(a, b)).collect();
+        BitGrid { words, ..*self }
+    }
+}
+
+// Each cell's western neighbour, moved into the cell's own bit
+fn west(row: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 { row[i - 1] >> 63 } else { 0 };
+    row[i] << 1 | carry
+}
+
+// Each cell's eastern neighbour, moved into the cell's own bit
+fn east(row: &[u64], i: usize) -> u64 {
+    let carry = if i + 1 < row.len() { row[i + 1] << 63 } else { 0 };
+    row[i] >> 1 | carry
+}
+
+// Bits whose count, spread across the planes from least significant, is below `threshold`
+fn fewer_than(planes: &[u64; 4], threshold: usize) -> u64 {
+    (0..threshold.min(16)).fold(0, |acc, count| {
+        acc | planes.iter().enumerate().fold(!0, |eq, (k, &p)| eq & if count >> k & 1 == 1 { p } else { !p })
+    })
+}
+
+struct BitIter(u64);
+
+impl Iterator for BitIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let b = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn naive_count(grid: &BitGrid, x: i64, y: i64, neighbourhood: Neighbourhood) -> usize {
+        neighbourhood.offsets().iter().filter(|(dx, dy)| grid.get(x + dx, y + dy)).count()
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = BitGrid::new(70, 3);
+        grid.set(0, 0, true);
+        grid.set(69, 2, true);
+        grid.set(64, 1, true);
+        grid.set(64, 1, false);
+
+        assert!(grid.get(0, 0));
+        assert!(grid.get(69, 2));
+        assert!(!grid.get(64, 1));
+        assert!(!grid.get(-1, 0));
+        assert!(!grid.get(70, 2));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.ones().collect::<Vec<_>>(), vec![(0, 0), (69, 2)]);
+    }
+
+    #[test]
+    fn test_set_ops() {
+        let a = BitGrid::from_fn(5, 2, |x, _| x % 2 == 0);
+        let b = BitGrid::from_fn(5, 2, |_, y| y == 0);
+        assert_eq!(a.and(&b).ones().collect::<Vec<_>>(), vec![(0, 0), (2, 0), (4, 0)]);
+        assert_eq!(a.or(&b).count_ones(), 8);
+        assert_eq!(a.and_not(&b).ones().collect::<Vec<_>>(), vec![(0, 1), (2, 1), (4, 1)]);
+        assert!(!a.and_not(&a).any());
+    }
+
+    #[test]
+    fn test_neighbours_fewer_than() {
+        let mut rng = StdRng::seed_from_u64(39);
+
+        for width in [1, 5, 63, 64, 65, 130] {
+            for height in [1, 2, 7] {
+                let density = rng.random_range(0.2..0.9);
+                let grid = BitGrid::from_fn(width, height, |_, _| rng.random_bool(density));
+
+                for neighbourhood in [Neighbourhood::Orthogonal, Neighbourhood::Surrounding] {
+                    for threshold in 0..=9 {
+                        let fast = grid.neighbours_fewer_than(threshold, neighbourhood);
+                        let slow = BitGrid::from_fn(width, height, |x, y| {
+                            naive_count(&grid, x as i64, y as i64, neighbourhood) < threshold
+                        });
+                        assert_eq!(fast, slow, "{}x{} {:?} < {}", width, height, neighbourhood, threshold);
+                    }
+                }
+            }
+        }
+    }
+}
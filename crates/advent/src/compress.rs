@@ -0,0 +1,147 @@
+/// Maps a sparse set of coordinate values onto dense indices.
+///
+/// Alongside the plain value indices, the values can be viewed as cells: each value gets
+/// an even cell index, and the open gap up to the next value the odd index after it. Any
+/// coordinate between the smallest and largest value falls in exactly one cell.
+#[derive(Debug, Clone, Default)]
+pub struct Compressor {
+    values: Vec<i64>,
+}
+
+impl Compressor {
+    pub fn new(values: impl IntoIterator<Item = i64>) -> Self {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        Compressor { values }
+    }
+
+    pub fn len(&self) -> usize { self.values.len() }
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+    pub fn values(&self) -> &[i64] { &self.values }
+
+    pub fn index(&self, v: i64) -> Option<usize> {
+        self.values.binary_search(&v).ok()
+    }
+
+    pub fn value(&self, index: usize) -> i64 {
+        self.values[index]
+    }
+
+    pub fn cell_count(&self) -> usize {
+        (self.values.len() * 2).saturating_sub(1)
+    }
+
+    /// The cell holding `v`, or none if it is outside the range of values.
+    pub fn cell(&self, v: i64) -> Option<usize> {
+        match self.values.binary_search(&v) {
+            Ok(i) => Some(i * 2),
+            Err(i) if i > 0 && i < self.values.len() => Some(i * 2 - 1),
+            Err(_) => None,
+        }
+    }
+
+    /// The inclusive range of integers in a cell, or none for a gap between consecutive integers.
+    pub fn cell_span(&self, cell: usize) -> Option<(i64, i64)> {
+        let i = cell / 2;
+        if cell.is_multiple_of(2) {
+            return Some((self.values[i], self.values[i]));
+        }
+
+        let (lo, hi) = (self.values[i] + 1, self.values[i + 1] - 1);
+        (lo <= hi).then_some((lo, hi))
+    }
+}
+
+/// Summed-area table answering rectangle sums over a 2D grid in constant time.
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D {
+    width: usize,
+    height: usize,
+    sums: Vec<i64>,
+}
+
+impl PrefixSum2D {
+    pub fn new(width: usize, height: usize, value: impl Fn(usize, usize) -> i64) -> Self {
+        let stride = width + 1;
+        let mut sums = vec![0; stride * (height + 1)];
+
+        for y in 0..height {
+            for x in 0..width {
+                sums[(y + 1) * stride + x + 1] = value(x, y)
+                    + sums[y * stride + x + 1]
+                    + sums[(y + 1) * stride + x]
+                    - sums[y * stride + x];
+            }
+        }
+
+        PrefixSum2D { width, height, sums }
+    }
+
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+
+    /// Sum of the cells from `(x1, y1)` to `(x2, y2)` inclusive.
+    pub fn sum(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> i64 {
+        let stride = self.width + 1;
+        self.sums[(y2 + 1) * stride + x2 + 1]
+            - self.sums[y1 * stride + x2 + 1]
+            - self.sums[(y2 + 1) * stride + x1]
+            + self.sums[y1 * stride + x1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressor() {
+        let c = Compressor::new([40, 7, 12, 7, 13, 40]);
+        assert_eq!(c.values(), &[7, 12, 13, 40]);
+        assert_eq!(c.index(13), Some(2));
+        assert_eq!(c.index(14), None);
+        assert_eq!(c.value(3), 40);
+    }
+
+    #[test]
+    fn test_compressor_cells() {
+        let c = Compressor::new([7, 12, 13, 40]);
+        assert_eq!(c.cell_count(), 7);
+        assert_eq!(c.cell(7), Some(0));
+        assert_eq!(c.cell(9), Some(1));
+        assert_eq!(c.cell(12), Some(2));
+        assert_eq!(c.cell(39), Some(5));
+        assert_eq!(c.cell(40), Some(6));
+        assert_eq!(c.cell(6), None);
+        assert_eq!(c.cell(41), None);
+
+        assert_eq!(c.cell_span(1), Some((8, 11)));
+        assert_eq!(c.cell_span(2), Some((12, 12)));
+        assert_eq!(c.cell_span(3), None);
+    }
+
+    #[test]
+    fn test_prefix_sum() {
+        let grid = [
+            [3, 0, 1, 4],
+            [5, 6, 3, 2],
+            [1, 2, 0, 1],
+        ];
+        let ps = PrefixSum2D::new(4, 3, |x, y| grid[y][x]);
+
+        assert_eq!(ps.sum(0, 0, 3, 2), 28);
+        assert_eq!(ps.sum(1, 1, 2, 2), 11);
+        assert_eq!(ps.sum(3, 0, 3, 0), 4);
+        assert_eq!(ps.sum(0, 1, 3, 1), 16);
+
+        for (y1, x1) in [(0, 0), (1, 2), (2, 3)] {
+            for (y2, x2) in [(2, 3), (2, 2)] {
+                if x1 <= x2 && y1 <= y2 {
+                    let brute = (y1..=y2).flat_map(|y| (x1..=x2).map(move |x| grid[y][x])).sum::<i64>();
+                    assert_eq!(ps.sum(x1, y1, x2, y2), brute);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Project settings from `aoc.toml`, found by walking up from the working directory.
+//! Environment variables override the file, and command line flags override both.
+//!
+//! ```toml
+//! year = 2025
+//! input_dir = "years/{year}/input"
+//! log = "info"
+//!
+//! [day.7]
+//! input = "a"
+//! visualize = true
+//! ```
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The year tools and runners use when none is given.
+    pub year: u32,
+    /// Directories for puzzle files, relative to `aoc.toml`, with `{year}` standing for
+    /// the year.
+    pub input_dir: String,
+    pub problem_dir: String,
+    pub answer_dir: String,
+    pub log: LogLevel,
+    pub visualize: bool,
+    #[serde(rename = "day")]
+    days: BTreeMap<String, DayConfig>,
+    /// The directory holding `aoc.toml`, or the working directory without one.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+/// Settings for one day, each falling back to the project setting.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayConfig {
+    /// The input variant to solve, such as `a` for `07a.txt`.
+    pub input: Option<String>,
+    pub log: Option<LogLevel>,
+    pub visualize: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    #[default]
+    Off,
+    Info,
+    Debug,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Toml(String),
+    BadDay(String),
+    BadValue { name: String, value: String },
+    MissingValue(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ConfigError::Toml(e) => write!(f, "{}: {}", FILE_NAME, e.trim()),
+            ConfigError::BadDay(key) => write!(f, "{}: [day.{}] is not a day number", FILE_NAME, key),
+            ConfigError::BadValue { name, value } => write!(f, "{}: unexpected value {:?}", name, value),
+            ConfigError::MissingValue(name) => write!(f, "{} needs a value", name),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl FromStr for LogLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(LogLevel::Off),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2025,
+            input_dir: "years/{year}/input".to_owned(),
+            problem_dir: "years/{year}/problem".to_owned(),
+            answer_dir: "years/{year}/answer".to_owned(),
+            log: LogLevel::Off,
+            visualize: false,
+            days: BTreeMap::new(),
+            root: PathBuf::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(s: &str, root: &Path) -> Result<Self, ConfigError> {
+        let config = toml::from_str::<Config>(s).map_err(|e| ConfigError::Toml(e.to_string()))?;
+
+        if let Some(key) = config.days.keys().find(|k| k.parse::<u32>().is_err()) {
+            return Err(ConfigError::BadDay(key.clone()));
+        }
+
+        Ok(Config { root: root.to_owned(), ..config })
+    }
+
+    /// The nearest `aoc.toml` at or above the working directory, or the defaults if there
+    /// is none, with the `AOC_*` environment variables applied.
+    pub fn load() -> Result<Self, ConfigError> {
+        let cwd = std::env::current_dir().map_err(|e| ConfigError::Io(PathBuf::from("."), e))?;
+
+        let mut config = match cwd.ancestors().map(|dir| dir.join(FILE_NAME)).find(|path| path.is_file()) {
+            Some(path) => {
+                let text = fs::read_to_string(&path).map_err(|e| ConfigError::Io(path.clone(), e))?;
+                Config::from_toml(&text, path.parent().unwrap())?
+            }
+            None => Config { root: cwd, ..Config::default() },
+        };
+
+        config.apply_env(|name| std::env::var(name).ok())?;
+        Ok(config)
+    }
+
+    /// Overrides settings from `AOC_YEAR`, `AOC_INPUT_DIR`, `AOC_PROBLEM_DIR`,
+    /// `AOC_ANSWER_DIR`, `AOC_LOG` and `AOC_VISUALIZE`.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        for name in ["AOC_YEAR", "AOC_INPUT_DIR", "AOC_PROBLEM_DIR", "AOC_ANSWER_DIR", "AOC_LOG", "AOC_VISUALIZE"] {
+            if let Some(value) = var(name) {
+                self.set(name, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Overrides settings from `--year`, `--input-dir`, `--problem-dir`, `--answer-dir`,
+    /// `--log` and `--visualize` flags, returning the other arguments in order.
+    pub fn apply_args(&mut self, args: &[String]) -> Result<Vec<String>, ConfigError> {
+        let mut rest = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--visualize" => self.visualize = true,
+                "--year" | "--input-dir" | "--problem-dir" | "--answer-dir" | "--log" => {
+                    let value = args.next().ok_or_else(|| ConfigError::MissingValue(arg.clone()))?;
+                    self.set(arg, value)?;
+                }
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        Ok(rest)
+    }
+
+    // Sets a value by its environment variable or flag name
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        let bad = || ConfigError::BadValue { name: name.to_owned(), value: value.to_owned() };
+
+        match name.trim_start_matches("AOC_").trim_start_matches("--").to_lowercase().replace('-', "_").as_str() {
+            "year" => self.year = value.parse().map_err(|_| bad())?,
+            "input_dir" => self.input_dir = value.to_owned(),
+            "problem_dir" => self.problem_dir = value.to_owned(),
+            "answer_dir" => self.answer_dir = value.to_owned(),
+            "log" => self.log = value.parse().map_err(|_| bad())?,
+            "visualize" => self.visualize = match value {
+                "1" | "true" => true,
+                "0" | "false" => false,
+                _ => return Err(bad()),
+            },
+            _ => return Err(bad()),
+        }
+        Ok(())
+    }
+
+    pub fn input_dir(&self, year: u32) -> PathBuf {
+        self.dir(&self.input_dir, year)
+    }
+
+    pub fn problem_dir(&self, year: u32) -> PathBuf {
+        self.dir(&self.problem_dir, year)
+    }
+
+    pub fn answer_dir(&self, year: u32) -> PathBuf {
+        self.dir(&self.answer_dir, year)
+    }
+
+    fn dir(&self, template: &str, year: u32) -> PathBuf {
+        self.root.join(template.replace("{year}", &year.to_string()))
+    }
+
+    pub fn day(&self, day: u32) -> DayConfig {
+        self.days.iter()
+            .find(|(key, _)| key.parse() == Ok(day))
+            .map(|(_, d)| d.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn log_level(&self, day: u32) -> LogLevel {
+        self.day(day).log.unwrap_or(self.log)
+    }
+
+    pub fn visualize(&self, day: u32) -> bool {
+        self.day(day).visualize.unwrap_or(self.visualize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+year = 2024
+input_dir = "inputs/{year}"
+log = "info"
+
+[day.7]
+input = "a"
+visualize = true
+
+[day.08]
+log = "debug"
+"#;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml(CONFIG, Path::new("/repo")).unwrap();
+
+        assert_eq!(config.year, 2024);
+        assert_eq!(config.input_dir(2024), Path::new("/repo/inputs/2024"));
+        assert_eq!(config.problem_dir(2017), Path::new("/repo/years/2017/problem"));
+        assert_eq!(config.day(7).input.as_deref(), Some("a"));
+        assert!(config.visualize(7));
+        assert!(!config.visualize(8));
+        assert_eq!(config.log_level(7), LogLevel::Info);
+        assert_eq!(config.log_level(8), LogLevel::Debug);
+        assert_eq!(config.day(9), DayConfig::default());
+
+        assert_eq!(Config::from_toml("", Path::new("")).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        assert!(matches!(Config::from_toml("year = \"soon\"", Path::new("")), Err(ConfigError::Toml(_))));
+        assert!(matches!(Config::from_toml("colour = true", Path::new("")), Err(ConfigError::Toml(_))));
+        assert!(matches!(Config::from_toml("[day.seven]", Path::new("")), Err(ConfigError::BadDay(k)) if k == "seven"));
+        assert!(matches!(Config::from_toml("[day.7]\ninput = 1", Path::new("")), Err(ConfigError::Toml(_))));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut config = Config::from_toml(CONFIG, Path::new("")).unwrap();
+
+        let env = BTreeMap::from([("AOC_YEAR", "2023"), ("AOC_LOG", "off"), ("AOC_ANSWER_DIR", "answers")]);
+        config.apply_env(|name| env.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.log, LogLevel::Off);
+        assert_eq!(config.answer_dir(2023), Path::new("answers"));
+
+        // Flags win over the environment
+        let rest = config.apply_args(&args("7 --year 2022 --visualize --log debug --input a")).unwrap();
+        assert_eq!(rest, args("7 --input a"));
+        assert_eq!(config.year, 2022);
+        assert!(config.visualize);
+        assert_eq!(config.log_level(9), LogLevel::Debug);
+
+        assert!(matches!(config.apply_args(&args("--year")), Err(ConfigError::MissingValue(_))));
+        assert!(matches!(config.apply_args(&args("--log loud")), Err(ConfigError::BadValue { .. })));
+        assert!(config.apply_env(|name| (name == "AOC_VISUALIZE").then(|| "maybe".to_owned())).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use crate::Point64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+
+/// Runs two solvers over `cases` random inputs, growing in size from 1 to `max_size`, and
+/// panics at the first input they disagree on. Case `n` is generated from seed `n`, so a
+/// failure can be replayed from the seed and size it reports.
+pub fn differential<T, G, F, S>(cases: u64, max_size: usize, generate: G, fast: F, slow: S)
+where
+    T: PartialEq + Debug,
+    G: Fn(&mut StdRng, usize) -> String,
+    F: Fn(&str) -> T,
+    S: Fn(&str) -> T,
+{
+    for seed in 0..cases {
+        let size = 1 + (seed as usize * max_size.saturating_sub(1)) / (cases.max(2) - 1) as usize;
+        let input = generate(&mut StdRng::seed_from_u64(seed), size);
+
+        let (f, s) = (fast(&input), slow(&input));
+        if f != s {
+            let shown = input.chars().take(2_000).collect::<String>();
+            panic!("solvers disagree on seed {} size {}\nfast: {:?}\nslow: {:?}\ninput:\n{}", seed, size, f, s, shown);
+        }
+    }
+}
+
+/// Joins lines the way the puzzle inputs are split.
+pub fn join_lines<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> String {
+    lines.into_iter().map(|l| l.as_ref().to_owned()).collect::<Vec<_>>().join("\r\n")
+}
+
+/// The corners of a random rectilinear loop with no holes or self contact, made by growing
+/// a region of cells on a `size` by `size` grid and tracing its outline. The gaps between
+/// grid lines are stretched to random widths of 1 to 3, so edges can run next to each
+/// other with no tiles between.
+pub fn rectilinear_loop(rng: &mut impl Rng, size: i64) -> Vec<Point64> {
+    let size = size.max(1);
+
+    loop {
+        let mut cells = BTreeSet::from([(size / 2, size / 2)]);
+        for _ in 0..rng.random_range(1..size * size + 1) {
+            let &(x, y) = cells.iter().nth(rng.random_range(0..cells.len())).unwrap();
+            let (dx, dy) = [(0, 1), (1, 0), (0, -1), (-1, 0)][rng.random_range(0..4)];
+            if (0..size).contains(&(x + dx)) && (0..size).contains(&(y + dy)) {
+                cells.insert((x + dx, y + dy));
+            }
+        }
+
+        if let Some(outline) = trace_outline(&cells, size) {
+            let mut stretch = |n: i64| (0..=n).scan(0, |acc, _| { *acc += rng.random_range(1..=3); Some(*acc) }).collect::<Vec<_>>();
+            let (sx, sy) = (stretch(size), stretch(size));
+            return outline.iter().map(|&(x, y)| Point64::new(sx[x as usize], sy[y as usize])).collect();
+        }
+    }
+}
+
+fn trace_outline(cells: &BTreeSet<(i64, i64)>, size: i64) -> Option<Vec<(i64, i64)>> {
+    let has = |x: i64, y: i64| cells.contains(&(x, y));
+
+    // Reject regions that touch only at a corner
+    for y in -1..size {
+        for x in -1..size {
+            let (a, b, c, d) = (has(x, y), has(x + 1, y), has(x, y + 1), has(x + 1, y + 1));
+            if (a && d && !b && !c) || (b && c && !a && !d) {
+                return None;
+            }
+        }
+    }
+
+    // Reject regions with holes
+    let mut seen = HashSet::from([(-1, -1)]);
+    let mut queue = VecDeque::from([(-1, -1)]);
+    while let Some((x, y)) = queue.pop_front() {
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if (-1..=size).contains(&nx) && (-1..=size).contains(&ny) && !has(nx, ny) && seen.insert((nx, ny)) {
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    if seen.len() + cells.len() != ((size + 2) * (size + 2)) as usize {
+        return None;
+    }
+
+    let mut next = HashMap::new();
+    for &(x, y) in cells {
+        if !has(x, y - 1) { next.insert((x, y), (x + 1, y)); }
+        if !has(x + 1, y) { next.insert((x + 1, y), (x + 1, y + 1)); }
+        if !has(x, y + 1) { next.insert((x + 1, y + 1), (x, y + 1)); }
+        if !has(x - 1, y) { next.insert((x, y + 1), (x, y)); }
+    }
+
+    let start = *next.keys().min().unwrap();
+    let mut path = vec![start];
+    let mut cur = next[&start];
+    while cur != start {
+        path.push(cur);
+        cur = next[&cur];
+    }
+
+    // Keep only the corners
+    let n = path.len();
+    let corners = (0..n)
+        .filter(|&i| {
+            let (p, c, q) = (path[(i + n - 1) % n], path[i], path[(i + 1) % n]);
+            !(p.0 == c.0 && c.0 == q.0 || p.1 == c.1 && c.1 == q.1)
+        })
+        .map(|i| path[i])
+        .collect::<Vec<_>>();
+
+    Some(corners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polygon::Polygon;
+
+    #[test]
+    fn test_differential_agrees() {
+        let sizes = std::cell::RefCell::new(Vec::new());
+        differential(5, 9, |rng, size| {
+            sizes.borrow_mut().push(size);
+            (0..size).map(|_| rng.random_range(0..10).to_string()).collect::<String>()
+        }, |s| s.bytes().map(|b| (b - b'0') as u32).sum::<u32>(), |s| s.chars().filter_map(|c| c.to_digit(10)).sum());
+
+        assert_eq!(*sizes.borrow(), vec![1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "solvers disagree on seed")]
+    fn test_differential_disagrees() {
+        differential(20, 10, |_, size| "x".repeat(size), |s| s.len(), |s| s.len().min(5));
+    }
+
+    #[test]
+    fn test_join_lines() {
+        assert_eq!(join_lines(["a", "b"]), "a\r\nb");
+        assert_eq!(join_lines(Vec::<String>::new()), "");
+    }
+
+    #[test]
+    fn test_rectilinear_loop() {
+        let mut rng = StdRng::seed_from_u64(45);
+        for size in 1..10 {
+            let poly = Polygon::new(rectilinear_loop(&mut rng, size));
+            assert!(poly.validate().is_ok(), "{:?}", poly.vertices());
+            assert!(poly.vertices().len() >= 4);
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use crate::{Board2D, Point64};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A graph given by the nodes reachable in one step from each node.
+///
+/// Implemented for closures `Fn(&N) -> impl IntoIterator<Item = N>`, so implicit state
+/// graphs need no type of their own.
+pub trait Neighbours<N> {
+    fn neighbours(&self, node: &N) -> Vec<N>;
+}
+
+/// A graph whose steps carry a cost.
+///
+/// Implemented for closures `Fn(&N) -> impl IntoIterator<Item = (N, W)>`.
+pub trait WeightedNeighbours<N, W> {
+    fn edges(&self, node: &N) -> Vec<(N, W)>;
+}
+
+impl<N, F, I> Neighbours<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbours(&self, node: &N) -> Vec<N> {
+        self(node).into_iter().collect()
+    }
+}
+
+impl<N, W, F, I> WeightedNeighbours<N, W> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, W)>,
+{
+    fn edges(&self, node: &N) -> Vec<(N, W)> {
+        self(node).into_iter().collect()
+    }
+}
+
+const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// Every cell of a board steps to the cells beside it; filter through a closure to add walls
+impl<T> Neighbours<Point64> for Board2D<T> {
+    fn neighbours(&self, node: &Point64) -> Vec<Point64> {
+        ORTHOGONAL.iter()
+            .map(|(dx, dy)| Point64::new(node.x + dx, node.y + dy))
+            .filter(|p| self.index(p.x, p.y).is_some())
+            .collect()
+    }
+}
+
+// Grids from `input_as_grid` step to the orthogonal cells that exist
+impl<V> Neighbours<(i64, i64)> for HashMap<(i64, i64), V> {
+    fn neighbours(&self, node: &(i64, i64)) -> Vec<(i64, i64)> {
+        ORTHOGONAL.iter()
+            .map(|(dx, dy)| (node.0 + dx, node.1 + dy))
+            .filter(|p| self.contains_key(p))
+            .collect()
+    }
+}
+
+/// Fewest steps from `start` to every reachable node.
+pub fn bfs<N, G>(graph: &G, start: N) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let d = dist[&node];
+        for next in graph.neighbours(&node) {
+            if !dist.contains_key(&next) {
+                dist.insert(next.clone(), d + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    dist
+}
+
+/// A path with the fewest steps from `start` to the first node accepted by `goal`.
+pub fn bfs_path<N, G>(graph: &G, start: N, goal: impl Fn(&N) -> bool) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let mut prev: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return Some(unwind(&prev, node));
+        }
+
+        for next in graph.neighbours(&node) {
+            if !prev.contains_key(&next) {
+                prev.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Every node reachable from `start`, in depth-first preorder.
+pub fn dfs<N, G>(graph: &G, start: N) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        // Push in reverse so neighbours are visited in the order they were given
+        stack.extend(graph.neighbours(&node).into_iter().rev().filter(|n| !seen.contains(n)));
+        order.push(node);
+    }
+
+    order
+}
+
+/// Lowest total cost from `start` to every reachable node.
+pub fn dijkstra<N, W, G>(graph: &G, start: N) -> HashMap<N, W>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Add<Output = W> + Default,
+    G: WeightedNeighbours<N, W>,
+{
+    let mut search = Search::new(start);
+    while search.step(graph, |_| W::default()).is_some() {}
+
+    search.nodes.into_iter().zip(search.cost).filter_map(|(n, c)| c.map(|c| (n, c))).collect()
+}
+
+/// The cheapest path from `start` to the first node accepted by `goal`, with its cost.
+pub fn dijkstra_path<N, W, G>(graph: &G, start: N, goal: impl Fn(&N) -> bool) -> Option<(W, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Add<Output = W> + Default,
+    G: WeightedNeighbours<N, W>,
+{
+    astar(graph, start, goal, |_| W::default())
+}
+
+/// The cheapest path from `start` to the first node accepted by `goal`, guided by a
+/// `heuristic` estimate of the remaining cost.
+///
+/// Each node is settled once, so the heuristic must be consistent: it never falls by more
+/// than the cost of a step, and is zero at a goal.
+pub fn astar<N, W, G>(graph: &G, start: N, goal: impl Fn(&N) -> bool, heuristic: impl Fn(&N) -> W) -> Option<(W, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Add<Output = W> + Default,
+    G: WeightedNeighbours<N, W>,
+{
+    let mut search = Search::new(start);
+
+    while let Some(i) = search.step(graph, &heuristic) {
+        if goal(&search.nodes[i]) {
+            let mut path = vec![search.nodes[i].clone()];
+            let mut cur = i;
+            while let Some(p) = search.prev[cur] {
+                path.push(search.nodes[p].clone());
+                cur = p;
+            }
+            path.reverse();
+            return search.cost[i].map(|c| (c, path));
+        }
+    }
+
+    None
+}
+
+// Best-first search shared by Dijkstra and A*. Nodes are numbered as they are found so
+// the heap never needs to compare them.
+struct Search<N, W> {
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    cost: Vec<Option<W>>,
+    prev: Vec<Option<usize>>,
+    done: Vec<bool>,
+    heap: BinaryHeap<Reverse<(W, usize)>>,
+}
+
+impl<N, W> Search<N, W>
+where
+    N: Clone + Eq + Hash,
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    fn new(start: N) -> Self {
+        Search {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![start],
+            cost: vec![Some(W::default())],
+            prev: vec![None],
+            done: vec![false],
+            heap: BinaryHeap::from([Reverse((W::default(), 0))]),
+        }
+    }
+
+    // Settles the next node and returns its number, or none once the search is exhausted
+    fn step<G: WeightedNeighbours<N, W>>(&mut self, graph: &G, heuristic: impl Fn(&N) -> W) -> Option<usize> {
+        while let Some(Reverse((_, i))) = self.heap.pop() {
+            if self.done[i] {
+                continue;
+            }
+            self.done[i] = true;
+
+            let base = self.cost[i].unwrap();
+            for (next, w) in graph.edges(&self.nodes[i]) {
+                let j = *self.index.entry(next.clone()).or_insert_with(|| {
+                    self.nodes.push(next.clone());
+                    self.cost.push(None);
+                    self.prev.push(None);
+                    self.done.push(false);
+                    self.nodes.len() - 1
+                });
+
+                let cost = base + w;
+                if !self.done[j] && self.cost[j].is_none_or(|c| cost < c) {
+                    self.cost[j] = Some(cost);
+                    self.prev[j] = Some(i);
+                    self.heap.push(Reverse((cost + heuristic(&next), j)));
+                }
+            }
+
+            return Some(i);
+        }
+
+        None
+    }
+}
+
+/// Orders `nodes` so every edge between them points forward, or none if they contain a cycle.
+///
+/// Nodes with no ordering between them keep the order they were given in. Edges to nodes
+/// outside `nodes` are ignored.
+pub fn topo_sort<N, G>(graph: &G, nodes: &[N]) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let index = nodes.iter().enumerate().map(|(i, n)| (n.clone(), i)).collect::<HashMap<_, _>>();
+    let edges = nodes.iter()
+        .map(|n| graph.neighbours(n).iter().filter_map(|m| index.get(m).copied()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut incoming = vec![0; nodes.len()];
+    edges.iter().flatten().for_each(|&j| incoming[j] += 1);
+
+    let mut ready = (0..nodes.len()).filter(|&i| incoming[i] == 0).map(Reverse).collect::<BinaryHeap<_>>();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(nodes[i].clone());
+        for &j in &edges[i] {
+            incoming[j] -= 1;
+            if incoming[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    (order.len() == nodes.len()).then_some(order)
+}
+
+/// Number of distinct paths from `start` to every node reachable from it, or none if a
+/// cycle is reachable.
+pub fn count_paths<N, G>(graph: &G, start: N) -> Option<HashMap<N, u64>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbours<N>,
+{
+    let reachable = dfs(graph, start.clone());
+    let order = topo_sort(graph, &reachable)?;
+
+    let mut ways = HashMap::from([(start, 1)]);
+    for node in order {
+        let n = ways.get(&node).copied().unwrap_or(0);
+        for next in graph.neighbours(&node) {
+            *ways.entry(next).or_insert(0) += n;
+        }
+    }
+
+    Some(ways)
+}
+
+fn unwind<N: Clone + Eq + Hash>(prev: &HashMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(Some(p)) = prev.get(path.last().unwrap()) {
+        path.push(p.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_as_grid;
+
+    fn maze() -> HashMap<(i64, i64), char> {
+        input_as_grid(concat!(
+            "S.#.....\r\n",
+            ".##.###.\r\n",
+            "....#...\r\n",
+            ".##...#E",
+        ))
+    }
+
+    fn open_cells(grid: &HashMap<(i64, i64), char>) -> impl Fn(&(i64, i64)) -> Vec<(i64, i64)> + '_ {
+        move |p| grid.neighbours(p).into_iter().filter(|q| grid[q] != '#').collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let dist = bfs(&open_cells(&grid), (0, 0));
+        assert_eq!(dist[&(7, 3)], 12);
+        assert_eq!(dist[&(3, 0)], 7);
+        assert!(!dist.contains_key(&(2, 0)));
+
+        let path = bfs_path(&open_cells(&grid), (0, 0), |p| grid[p] == 'E').unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(7, 3)));
+        assert!(path.windows(2).all(|w| (w[0].0 - w[1].0).abs() + (w[0].1 - w[1].1).abs() == 1));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let graph = |n: &u32| match n { 0 => vec![1, 2], 1 => vec![3], 2 => vec![3], _ => vec![] };
+        assert_eq!(dfs(&graph, 0), vec![0, 1, 3, 2]);
+        assert_eq!(dfs(&graph, 2), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_board_neighbours() {
+        let board = Board2D::new(vec![0; 6], 2, 3);
+        assert_eq!(board.neighbours(&Point64::new(0, 0)), vec![Point64::new(1, 0), Point64::new(0, 1)]);
+        assert_eq!(bfs(&board, Point64::new(0, 0))[&Point64::new(2, 1)], 3);
+    }
+
+    // Cost of entering a cell is its digit, so the cheapest path can take more steps
+    fn weighted_grid() -> HashMap<(i64, i64), char> {
+        input_as_grid("1163751\r\n1381373\r\n2136511\r\n3694931\r\n7463417")
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid = weighted_grid();
+        let graph = |p: &(i64, i64)| grid.neighbours(p).into_iter().map(|q| (q, grid[&q].to_digit(10).unwrap() as u64));
+
+        let cost = dijkstra(&graph, (0, 0));
+        assert_eq!(cost.len(), 35);
+        assert_eq!(cost[&(0, 0)], 0);
+        assert_eq!(cost[&(1, 0)], 1);
+
+        let (total, path) = dijkstra_path(&graph, (0, 0), |&p| p == (6, 4)).unwrap();
+        assert_eq!(total, cost[&(6, 4)]);
+        assert_eq!(total, path.iter().skip(1).map(|q| grid[q].to_digit(10).unwrap() as u64).sum::<u64>());
+
+        let brute = brute_force_cost(&grid, (6, 4));
+        assert_eq!(total, brute);
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = weighted_grid();
+        let graph = |p: &(i64, i64)| grid.neighbours(p).into_iter().map(|q| (q, grid[&q].to_digit(10).unwrap() as u64));
+        let manhattan = |p: &(i64, i64)| ((6 - p.0) + (4 - p.1)) as u64;
+
+        let (total, path) = astar(&graph, (0, 0), |&p| p == (6, 4), manhattan).unwrap();
+        assert_eq!(total, brute_force_cost(&grid, (6, 4)));
+        assert_eq!(path.last(), Some(&(6, 4)));
+        assert_eq!(astar(&graph, (0, 0), |&p| p == (9, 9), manhattan), None);
+    }
+
+    // Relaxes every cell until nothing improves
+    fn brute_force_cost(grid: &HashMap<(i64, i64), char>, goal: (i64, i64)) -> u64 {
+        let mut cost = grid.keys().map(|&k| (k, u64::MAX)).collect::<HashMap<_, _>>();
+        cost.insert((0, 0), 0);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (&p, _) in grid.iter() {
+                for q in grid.neighbours(&p) {
+                    let c = cost[&p].saturating_add(grid[&q].to_digit(10).unwrap() as u64);
+                    if c < cost[&q] {
+                        cost.insert(q, c);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        cost[&goal]
+    }
+
+    #[test]
+    fn test_topo_sort() {
+        let graph = |n: &char| match n { 'a' => vec!['c'], 'b' => vec!['c', 'd'], 'c' => vec!['e'], 'd' => vec!['e', 'z'], _ => vec![] };
+        assert_eq!(topo_sort(&graph, &['e', 'd', 'c', 'b', 'a']), Some(vec!['b', 'd', 'a', 'c', 'e']));
+
+        let cyclic = |n: &u8| vec![(n + 1) % 3];
+        assert_eq!(topo_sort(&cyclic, &[0, 1, 2]), None);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // A beam moves down, splitting left and right at each `^`, as in Day 7
+        let grid = input_as_grid("...S...\r\n.......\r\n...^...\r\n.......\r\n..^.^..\r\n.......\r\n.^.^.^.\r\n.......");
+        let beam = |&(x, y): &(i64, i64)| match grid.get(&(x, y + 1)) {
+            Some('^') => vec![(x - 1, y + 1), (x + 1, y + 1)],
+            Some(_) => vec![(x, y + 1)],
+            None => vec![],
+        };
+
+        let ways = count_paths(&beam, (3, 0)).unwrap();
+        assert_eq!((0..7).map(|x| ways.get(&(x, 7)).copied().unwrap_or(0)).sum::<u64>(), 8);
+        assert_eq!(ways[&(3, 5)], 2);
+
+        let cyclic = |n: &u8| vec![(n + 1) % 3];
+        assert_eq!(count_paths(&cyclic, 0), None);
+    }
+}
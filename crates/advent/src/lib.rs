@@ -1,17 +1,41 @@
 
+pub mod bitgrid;
+pub mod compress;
+pub mod config;
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod graph;
+pub mod linalg;
+pub mod mst;
+pub mod number;
+pub mod ocr;
+pub mod par;
+pub mod parse;
+pub mod polygon;
+pub mod puzzle;
+pub mod run;
 pub mod shared;
+pub mod sim;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
+// Lets code generated by the derive macros name this crate from inside it
+extern crate self as advent;
+
+pub use advent_derive::ParseInput;
+
+/// Includes a day's puzzle input from the `input` directory of the year crate.
 #[macro_export]
 macro_rules! include_input {
     ($day:literal) => {
-        include_str!(concat!("./input/", stringify!($day), ".txt"))
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/", stringify!($day), ".txt"))
     };
 }
 
+/// The lines of an input, ending in `\n` or `\r\n`, with no empty line for a line break
+/// at the end.
 pub fn input_as_lines(s: &str) -> Vec<String> {
-    s.split("\r\n").map(|x| x.to_owned()).collect::<Vec<_>>()
+    s.lines().map(|x| x.to_owned()).collect::<Vec<_>>()
 }
 
 pub fn input_as_grid(s: &str) -> HashMap<(i64, i64), char> {
@@ -40,6 +64,34 @@ pub struct Point32 {
     pub y: i32,
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+pub struct Point64 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point64 {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point64 { x, y }
+    }
+}
+
+/// Which cells around a cell count as its neighbours.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Neighbourhood {
+    Orthogonal,
+    Surrounding,
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> &'static [(i64, i64)] {
+        match self {
+            Neighbourhood::Orthogonal => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbourhood::Surrounding => &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)],
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, Default)]
 pub enum Compass {
     #[default]
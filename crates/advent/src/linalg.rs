@@ -0,0 +1,485 @@
+use crate::number::gcd;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den);
+        let sign = if den < 0 { -1 } else { 1 };
+        Rational { num: sign * num / g, den: sign * den / g }
+    }
+
+    pub fn num(&self) -> i128 { self.num }
+    pub fn den(&self) -> i128 { self.den }
+
+    pub fn is_zero(&self) -> bool { self.num == 0 }
+    pub fn is_integer(&self) -> bool { self.den == 1 }
+
+    /// The value as an integer, or none if it has a fractional part or does not fit.
+    pub fn to_integer(&self) -> Option<i64> {
+        self.is_integer().then(|| i64::try_from(self.num).ok()).flatten()
+    }
+
+    pub fn recip(&self) -> Self {
+        Rational::new(self.den, self.num)
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self { Rational::ZERO }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational { num: n as i128, den: 1 }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            _ => write!(f, "{}/{}", self.num, self.den),
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, rhs: Self) -> Self {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, rhs: Self) -> Self {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, rhs: Self) -> Self {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Self {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+/// A dense matrix of rationals, stored row by row.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+/// Every solution of `A x = b`: the particular solution plus any combination of the
+/// nullspace basis, whose vectors each set one free variable to 1.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Solution {
+    pub particular: Vec<Rational>,
+    pub free: Vec<usize>,
+    pub basis: Vec<Vec<Rational>>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix { rows, cols, data: vec![Rational::ZERO; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Matrix::zeros(n, n);
+        (0..n).for_each(|i| m[(i, i)] = Rational::ONE);
+        m
+    }
+
+    pub fn from_rows(rows: &[Vec<i64>]) -> Self {
+        let cols = rows.first().map_or(0, |r| r.len());
+        assert!(rows.iter().all(|r| r.len() == cols), "rows differ in length");
+
+        let data = rows.iter().flatten().map(|&n| Rational::from(n)).collect();
+        Matrix { rows: rows.len(), cols, data }
+    }
+
+    pub fn rows(&self) -> usize { self.rows }
+    pub fn cols(&self) -> usize { self.cols }
+
+    pub fn row(&self, r: usize) -> &[Rational] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut t = Matrix::zeros(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t[(c, r)] = self[(r, c)];
+            }
+        }
+        t
+    }
+
+    pub fn mul_vec(&self, x: &[Rational]) -> Vec<Rational> {
+        assert_eq!(x.len(), self.cols);
+        (0..self.rows)
+            .map(|r| self.row(r).iter().zip(x).fold(Rational::ZERO, |acc, (&a, &b)| acc + a * b))
+            .collect()
+    }
+
+    /// Reduced row echelon form by Gauss-Jordan elimination, with the pivot column of each
+    /// non-zero row.
+    pub fn rref(&self) -> (Matrix, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = Vec::new();
+
+        for c in 0..m.cols {
+            let r = pivots.len();
+            let Some(p) = (r..m.rows).find(|&i| !m[(i, c)].is_zero()) else {
+                continue;
+            };
+
+            m.swap_rows(r, p);
+            let lead = m[(r, c)];
+            for j in 0..m.cols {
+                m[(r, j)] = m[(r, j)] / lead;
+            }
+
+            for i in (0..m.rows).filter(|&i| i != r) {
+                let factor = m[(i, c)];
+                if !factor.is_zero() {
+                    for j in 0..m.cols {
+                        let v = m[(r, j)];
+                        m[(i, j)] = m[(i, j)] - factor * v;
+                    }
+                }
+            }
+
+            pivots.push(c);
+            if pivots.len() == m.rows {
+                break;
+            }
+        }
+
+        (m, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// A basis of the vectors `x` with `A x = 0`.
+    pub fn nullspace(&self) -> Vec<Vec<Rational>> {
+        let zeros = vec![Rational::ZERO; self.rows];
+        self.solve(&zeros).map(|s| s.basis).unwrap_or_default()
+    }
+
+    /// Solves `A x = b`, or none if the system is inconsistent.
+    pub fn solve(&self, b: &[Rational]) -> Option<Solution> {
+        assert_eq!(b.len(), self.rows);
+
+        let mut augmented = Matrix::zeros(self.rows, self.cols + 1);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                augmented[(r, c)] = self[(r, c)];
+            }
+            augmented[(r, self.cols)] = b[r];
+        }
+
+        let (m, pivots) = augmented.rref();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let free = (0..self.cols).filter(|c| !pivots.contains(c)).collect::<Vec<_>>();
+
+        let mut particular = vec![Rational::ZERO; self.cols];
+        for (r, &c) in pivots.iter().enumerate() {
+            particular[c] = m[(r, self.cols)];
+        }
+
+        let basis = free.iter().map(|&f| {
+            let mut v = vec![Rational::ZERO; self.cols];
+            v[f] = Rational::ONE;
+            for (r, &c) in pivots.iter().enumerate() {
+                v[c] = -m[(r, f)];
+            }
+            v
+        }).collect();
+
+        Some(Solution { particular, free, basis })
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for c in 0..self.cols {
+                self.data.swap(a * self.cols + c, b * self.cols + c);
+            }
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+    fn index(&self, (r, c): (usize, usize)) -> &Rational {
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut Rational {
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+impl Solution {
+    /// The solution with the free variables set to `values`, in the order of `free`.
+    pub fn at(&self, values: &[Rational]) -> Vec<Rational> {
+        assert_eq!(values.len(), self.free.len());
+        let mut x = self.particular.clone();
+        for (v, &k) in self.basis.iter().zip(values) {
+            x.iter_mut().zip(v).for_each(|(xi, &vi)| *xi = *xi + vi * k);
+        }
+        x
+    }
+}
+
+/// Minimizes `cost · x` over integer `x` with `A x = b` and `0 <= x[i] <= upper[i]`.
+///
+/// The free variables of the system are searched exhaustively within their bounds, so this
+/// suits systems with few free variables. Returns the lowest cost and a solution with it,
+/// or none if no integer solution fits the bounds.
+pub fn minimize_integer(a: &Matrix, b: &[i64], cost: &[i64], upper: &[i64]) -> Option<(i64, Vec<i64>)> {
+    assert_eq!(cost.len(), a.cols());
+    assert_eq!(upper.len(), a.cols());
+
+    let b = b.iter().map(|&n| Rational::from(n)).collect::<Vec<_>>();
+    let solution = a.solve(&b)?;
+
+    let mut best = None;
+    let mut values = vec![Rational::ZERO; solution.free.len()];
+    search_free(&solution, cost, upper, 0, &mut values, &mut best);
+    best
+}
+
+fn search_free(
+    solution: &Solution,
+    cost: &[i64],
+    upper: &[i64],
+    depth: usize,
+    values: &mut [Rational],
+    best: &mut Option<(i64, Vec<i64>)>,
+) {
+    if depth == values.len() {
+        let x = solution.at(values);
+        let Some(x) = x.iter().map(|v| v.to_integer()).collect::<Option<Vec<_>>>() else {
+            return;
+        };
+
+        if x.iter().zip(upper).all(|(&xi, &u)| (0..=u).contains(&xi)) {
+            let total = x.iter().zip(cost).map(|(xi, c)| xi * c).sum::<i64>();
+            if best.as_ref().is_none_or(|(b, _)| total < *b) {
+                *best = Some((total, x));
+            }
+        }
+        return;
+    }
+
+    for v in 0..=upper[solution.free[depth]] {
+        values[depth] = Rational::from(v);
+        search_free(solution, cost, upper, depth + 1, values, best);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    fn ints(v: &[i64]) -> Vec<Rational> {
+        v.iter().map(|&n| Rational::from(n)).collect()
+    }
+
+    #[test]
+    fn test_rational() {
+        assert_eq!(r(6, -4), r(-3, 2));
+        assert_eq!(r(1, 3) + r(1, 6), r(1, 2));
+        assert_eq!(r(1, 3) - r(1, 2), r(-1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert!(r(-1, 2) < r(1, 3));
+        assert_eq!(r(8, 4).to_integer(), Some(2));
+        assert_eq!(r(7, 4).to_integer(), None);
+        assert_eq!(r(-7, 4).to_string(), "-7/4");
+        assert_eq!(r(0, -5), Rational::ZERO);
+    }
+
+    #[test]
+    fn test_rref() {
+        // Checked by hand: R2 -= 2 R1, R3 += R1, then clear upwards
+        let m = Matrix::from_rows(&[vec![1, 2, -1], vec![2, 3, 1], vec![-1, 0, 5]]);
+        let (reduced, pivots) = m.rref();
+        assert_eq!(pivots, vec![0, 1, 2]);
+        assert_eq!(reduced, Matrix::identity(3));
+
+        let m = Matrix::from_rows(&[vec![1, 2, 3], vec![2, 4, 6], vec![1, 0, 1]]);
+        let (reduced, pivots) = m.rref();
+        assert_eq!(pivots, vec![0, 1]);
+        assert_eq!(reduced.row(0), &[r(1, 1), r(0, 1), r(1, 1)]);
+        assert_eq!(reduced.row(1), &[r(0, 1), r(1, 1), r(1, 1)]);
+        assert!(reduced.row(2).iter().all(Rational::is_zero));
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.transpose().rank(), 2);
+    }
+
+    #[test]
+    fn test_solve_unique() {
+        // 2x + y = 3, x + 3y = 5 gives x = 4/5, y = 7/5
+        let m = Matrix::from_rows(&[vec![2, 1], vec![1, 3]]);
+        let s = m.solve(&ints(&[3, 5])).unwrap();
+        assert_eq!(s.particular, vec![r(4, 5), r(7, 5)]);
+        assert!(s.free.is_empty());
+        assert!(m.nullspace().is_empty());
+    }
+
+    #[test]
+    fn test_solve_inconsistent() {
+        // x + y = 1 and 2x + 2y = 3 cannot both hold
+        let m = Matrix::from_rows(&[vec![1, 1], vec![2, 2]]);
+        assert_eq!(m.solve(&ints(&[1, 3])), None);
+        assert!(m.solve(&ints(&[1, 2])).is_some());
+    }
+
+    #[test]
+    fn test_nullspace() {
+        // x + 2y + 3z = 0 has the plane spanned by (-2, 1, 0) and (-3, 0, 1)
+        let m = Matrix::from_rows(&[vec![1, 2, 3], vec![2, 4, 6]]);
+        let basis = m.nullspace();
+        assert_eq!(basis, vec![ints(&[-2, 1, 0]), ints(&[-3, 0, 1])]);
+
+        for v in &basis {
+            assert!(m.mul_vec(v).iter().all(Rational::is_zero));
+        }
+    }
+
+    #[test]
+    fn test_solution_at() {
+        let m = Matrix::from_rows(&[vec![1, 1, 0], vec![0, 1, 1]]);
+        let b = ints(&[5, 7]);
+        let s = m.solve(&b).unwrap();
+        assert_eq!(s.free, vec![2]);
+
+        for z in -3..4 {
+            let x = s.at(&[Rational::from(z)]);
+            assert_eq!(m.mul_vec(&x), b);
+            assert_eq!(x[2], Rational::from(z));
+        }
+    }
+
+    // Each button adds one to the counters it lists; press buttons to reach the targets
+    fn button_system(buttons: &[&[usize]], targets: &[i64]) -> (Matrix, Vec<i64>) {
+        let rows = (0..targets.len())
+            .map(|t| buttons.iter().map(|b| b.contains(&t) as i64).collect())
+            .collect::<Vec<_>>();
+        let upper = buttons.iter().map(|b| b.iter().map(|&t| targets[t]).min().unwrap()).collect();
+        (Matrix::from_rows(&rows), upper)
+    }
+
+    fn brute_force_presses(buttons: &[&[usize]], targets: &[i64], upper: &[i64]) -> Option<i64> {
+        fn go(i: usize, buttons: &[&[usize]], upper: &[i64], left: &mut [i64], presses: i64) -> Option<i64> {
+            if i == buttons.len() {
+                return left.iter().all(|&l| l == 0).then_some(presses);
+            }
+
+            let mut best = None;
+            for n in 0..=upper[i] {
+                if buttons[i].iter().any(|&t| left[t] < n) {
+                    break;
+                }
+                buttons[i].iter().for_each(|&t| left[t] -= n);
+                if let Some(p) = go(i + 1, buttons, upper, left, presses + n) {
+                    best = Some(best.map_or(p, |b: i64| b.min(p)));
+                }
+                buttons[i].iter().for_each(|&t| left[t] += n);
+            }
+            best
+        }
+
+        go(0, buttons, upper, &mut targets.to_vec(), 0)
+    }
+
+    // Buttons, counter targets and the fewest presses that reach them
+    type Machine = (&'static [&'static [usize]], &'static [i64], i64);
+
+    #[test]
+    fn test_minimize_button_presses() {
+        let machines: [Machine; 3] = [
+            (&[&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]], &[3, 5, 4, 7], 10),
+            (&[&[0, 2, 3, 4], &[2, 3], &[0, 4], &[0, 1, 2], &[1, 2, 3, 4]], &[7, 5, 12, 7, 2], 12),
+            (&[&[0, 1, 2, 3, 4], &[0, 3, 4], &[0, 1, 2, 4, 5], &[1, 2]], &[10, 11, 11, 5, 10, 5], 11),
+        ];
+
+        for (buttons, targets, expected) in machines {
+            let (m, upper) = button_system(buttons, targets);
+            let cost = vec![1; buttons.len()];
+            let (presses, x) = minimize_integer(&m, targets, &cost, &upper).unwrap();
+
+            assert_eq!(presses, expected);
+            assert_eq!(Some(presses), brute_force_presses(buttons, targets, &upper));
+            assert_eq!(m.mul_vec(&ints(&x)), ints(targets));
+        }
+    }
+
+    #[test]
+    fn test_minimize_infeasible() {
+        // x + y = 3 with x, y <= 1 has no solution, and 2x = 3 has no integer one
+        let m = Matrix::from_rows(&[vec![1, 1]]);
+        assert_eq!(minimize_integer(&m, &[3], &[1, 1], &[1, 1]), None);
+
+        let m = Matrix::from_rows(&[vec![2]]);
+        assert_eq!(minimize_integer(&m, &[3], &[1], &[5]), None);
+
+        // Weighted costs pick the cheaper variable
+        let m = Matrix::from_rows(&[vec![1, 1]]);
+        assert_eq!(minimize_integer(&m, &[4], &[3, 1], &[4, 4]), Some((4, vec![0, 4])));
+    }
+}
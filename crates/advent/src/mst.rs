@@ -0,0 +1,258 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// An undirected weighted edge between two vertices, identified by index.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Edge<W> {
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+}
+
+impl<W> Edge<W> {
+    pub fn new(from: usize, to: usize, weight: W) -> Self {
+        Edge { from, to, weight }
+    }
+}
+
+/// Union-find over `0..n` with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> Self {
+        DisjointSet { parent: (0..n).collect(), size: vec![1; n], count: n }
+    }
+
+    pub fn len(&self) -> usize { self.parent.len() }
+    pub fn is_empty(&self) -> bool { self.parent.is_empty() }
+
+    /// Number of disjoint components.
+    pub fn count(&self) -> usize { self.count }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+
+        let mut cur = x;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+
+        root
+    }
+
+    /// Joins the components of `a` and `b`, returning false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+
+        let (keep, fold) = if self.size[ra] >= self.size[rb] { (ra, rb) } else { (rb, ra) };
+        self.parent[fold] = keep;
+        self.size[keep] += self.size[fold];
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    pub fn size_of(&self, x: usize) -> usize {
+        self.size[self.root(x)]
+    }
+
+    /// Sizes of every component, largest first.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|x| self.size[x])
+            .collect::<Vec<_>>();
+
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    fn root(&self, x: usize) -> usize {
+        let mut cur = x;
+        while self.parent[cur] != cur {
+            cur = self.parent[cur];
+        }
+        cur
+    }
+}
+
+/// Steps through Kruskal's algorithm one candidate edge at a time.
+///
+/// Edges are visited in ascending weight order; edges of equal weight keep their
+/// order from the input list. Each item is the candidate edge and whether it joined
+/// two components. Iteration ends once every vertex is in a single component.
+#[derive(Debug, Clone)]
+pub struct Kruskal<W> {
+    sets: DisjointSet,
+    edges: std::vec::IntoIter<Edge<W>>,
+}
+
+impl<W: Ord + Copy> Kruskal<W> {
+    pub fn new(n: usize, edges: &[Edge<W>]) -> Self {
+        let mut sorted = edges.to_vec();
+        sorted.sort_by_key(|e| e.weight);
+
+        Kruskal { sets: DisjointSet::new(n), edges: sorted.into_iter() }
+    }
+
+    pub fn sets(&self) -> &DisjointSet {
+        &self.sets
+    }
+}
+
+impl<W: Ord + Copy> Iterator for Kruskal<W> {
+    type Item = (Edge<W>, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sets.count() <= 1 {
+            return None;
+        }
+
+        let edge = self.edges.next()?;
+        let joined = self.sets.union(edge.from, edge.to);
+        Some((edge, joined))
+    }
+}
+
+/// Minimum spanning forest by Kruskal's algorithm, edges in the order they were accepted.
+pub fn kruskal<W: Ord + Copy>(n: usize, edges: &[Edge<W>]) -> Vec<Edge<W>> {
+    Kruskal::new(n, edges)
+        .filter(|(_, joined)| *joined)
+        .map(|(edge, _)| edge)
+        .collect()
+}
+
+/// Minimum spanning forest by Prim's algorithm, edges in the order they were accepted.
+///
+/// Each tree is grown from its lowest numbered vertex.
+pub fn prim<W: Ord + Copy>(n: usize, edges: &[Edge<W>]) -> Vec<Edge<W>> {
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (i, e) in edges.iter().enumerate() {
+        adjacent[e.from].push(i);
+        adjacent[e.to].push(i);
+    }
+
+    let mut visited = vec![false; n];
+    let mut tree = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in 0..n {
+        if visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        heap.extend(adjacent[start].iter().map(|&i| Reverse((edges[i].weight, i))));
+
+        while let Some(Reverse((_, i))) = heap.pop() {
+            let e = edges[i];
+            let next = match (visited[e.from], visited[e.to]) {
+                (true, false) => e.to,
+                (false, true) => e.from,
+                _ => continue,
+            };
+
+            visited[next] = true;
+            tree.push(e);
+            heap.extend(adjacent[next].iter().map(|&i| Reverse((edges[i].weight, i))));
+        }
+    }
+
+    tree
+}
+
+pub fn total_weight<W: Copy + std::iter::Sum<W>>(edges: &[Edge<W>]) -> W {
+    edges.iter().map(|e| e.weight).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_edges() -> Vec<Edge<i64>> {
+        vec![
+            Edge::new(0, 1, 7),
+            Edge::new(0, 3, 5),
+            Edge::new(1, 2, 8),
+            Edge::new(1, 3, 9),
+            Edge::new(1, 4, 7),
+            Edge::new(2, 4, 5),
+            Edge::new(3, 4, 15),
+            Edge::new(3, 5, 6),
+            Edge::new(4, 5, 8),
+            Edge::new(4, 6, 9),
+            Edge::new(5, 6, 11),
+        ]
+    }
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut ds = DisjointSet::new(5);
+        assert_eq!(ds.count(), 5);
+        assert!(ds.union(0, 1));
+        assert!(ds.union(3, 4));
+        assert!(!ds.union(1, 0));
+        assert!(ds.union(1, 4));
+
+        assert_eq!(ds.count(), 2);
+        assert!(ds.connected(0, 3));
+        assert!(!ds.connected(0, 2));
+        assert_eq!(ds.size_of(4), 4);
+        assert_eq!(ds.sizes(), vec![4, 1]);
+    }
+
+    #[test]
+    fn test_kruskal() {
+        let tree = kruskal(7, &sample_edges());
+        assert_eq!(tree.len(), 6);
+        assert_eq!(total_weight(&tree), 39);
+        assert_eq!(tree[0], Edge::new(0, 3, 5));
+        assert_eq!(tree[1], Edge::new(2, 4, 5));
+        assert_eq!(tree[5], Edge::new(4, 6, 9));
+    }
+
+    #[test]
+    fn test_prim() {
+        let tree = prim(7, &sample_edges());
+        assert_eq!(tree.len(), 6);
+        assert_eq!(total_weight(&tree), 39);
+        assert_eq!(tree[0], Edge::new(0, 3, 5));
+        assert_eq!(tree[1], Edge::new(3, 5, 6));
+    }
+
+    #[test]
+    fn test_spanning_forest() {
+        let edges = vec![Edge::new(0, 1, 2), Edge::new(2, 3, 1), Edge::new(1, 0, 1)];
+        let k = kruskal(5, &edges);
+        let p = prim(5, &edges);
+
+        assert_eq!(k, vec![Edge::new(2, 3, 1), Edge::new(1, 0, 1)]);
+        assert_eq!(total_weight(&p), 2);
+        assert_eq!(p.len(), 2);
+    }
+
+    #[test]
+    fn test_kruskal_steps() {
+        let mut steps = Kruskal::new(7, &sample_edges());
+        let first = steps.by_ref().take(4).collect::<Vec<_>>();
+
+        assert_eq!(first.iter().filter(|(_, joined)| *joined).count(), 4);
+        assert_eq!(steps.sets().sizes(), vec![4, 2, 1]);
+
+        let rest = steps.collect::<Vec<_>>();
+        assert_eq!(rest.last(), Some(&(Edge::new(4, 6, 9), true)));
+    }
+}
@@ -0,0 +1,412 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The primitive integer operations the number theory functions are written against.
+pub trait Integer:
+    Copy + Ord + Debug
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn rem_euclid(self, m: Self) -> Self;
+    fn isqrt(self) -> Self;
+
+    /// `self * rhs` reduced into `0..m`, without overflowing for any positive `m`.
+    fn mul_mod(self, rhs: Self, m: Self) -> Self;
+
+    fn is_negative(self) -> bool {
+        self < Self::ZERO
+    }
+}
+
+/// Integers with a sign, needed wherever Bézout coefficients turn up.
+pub trait SignedInteger: Integer + Neg<Output = Self> {}
+
+macro_rules! integer_common {
+    ($t:ty) => {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+
+        fn checked_add(self, rhs: Self) -> Option<Self> { <$t>::checked_add(self, rhs) }
+        fn checked_mul(self, rhs: Self) -> Option<Self> { <$t>::checked_mul(self, rhs) }
+        fn rem_euclid(self, m: Self) -> Self { <$t>::rem_euclid(self, m) }
+        fn isqrt(self) -> Self { <$t>::isqrt(self) }
+    };
+}
+
+// Widths up to 64 bits multiply through a type twice as wide
+macro_rules! integer_impl {
+    ($($t:ty => $wide:ty),*) => {$(
+        impl Integer for $t {
+            integer_common!($t);
+
+            fn mul_mod(self, rhs: Self, m: Self) -> Self {
+                ((self as $wide * rhs as $wide).rem_euclid(m as $wide)) as $t
+            }
+        }
+    )*};
+}
+
+// 128 bit widths have nothing wider, so multiply by doubling and adding
+macro_rules! integer_impl_128 {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            integer_common!($t);
+
+            fn mul_mod(self, rhs: Self, m: Self) -> Self {
+                let add_mod = |x: Self, y: Self| if x >= m - y { x - (m - y) } else { x + y };
+                let (mut a, mut b) = (self.rem_euclid(m), rhs.rem_euclid(m));
+                let mut product = 0;
+
+                while b > 0 {
+                    if b & 1 == 1 {
+                        product = add_mod(product, a);
+                    }
+                    a = add_mod(a, a);
+                    b >>= 1;
+                }
+
+                product
+            }
+        }
+    )*};
+}
+
+integer_impl!(u8 => u16, u16 => u32, u32 => u64, u64 => u128, usize => u128);
+integer_impl!(i8 => i16, i16 => i32, i32 => i64, i64 => i128, isize => i128);
+integer_impl_128!(u128, i128);
+
+impl SignedInteger for i8 {}
+impl SignedInteger for i16 {}
+impl SignedInteger for i32 {}
+impl SignedInteger for i64 {}
+impl SignedInteger for i128 {}
+impl SignedInteger for isize {}
+
+fn abs<T: Integer>(n: T) -> T {
+    if n.is_negative() { T::ZERO - n } else { n }
+}
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Least common multiple, never negative, or none if it overflows. Zero if either is zero.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    abs(a / gcd(a, b)).checked_mul(abs(b))
+}
+
+/// Least common multiple, never negative. Zero if either is zero.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Least common multiple of every number, or none if it overflows. 1 for no numbers.
+pub fn checked_lcm_of<T: Integer>(nums: &[T]) -> Option<T> {
+    nums.iter().try_fold(T::ONE, |acc, &n| checked_lcm(acc, n))
+}
+
+/// Least common multiple of every number. 1 for no numbers.
+pub fn lcm_of<T: Integer>(nums: &[T]) -> T {
+    checked_lcm_of(nums).expect("lcm overflowed")
+}
+
+/// Returns `(g, x, y)` where `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd<T: SignedInteger>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, or none if `a` and `m` share a factor.
+pub fn mod_inverse<T: SignedInteger>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// `base` raised to `exp`, reduced into `0..m`.
+pub fn mod_pow<T: Integer>(base: T, exp: u64, m: T) -> T {
+    let mut result = T::ONE.rem_euclid(m);
+    let mut base = base.rem_euclid(m);
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul_mod(base, m);
+        }
+        base = base.mul_mod(base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` pair, which need not have coprime moduli.
+///
+/// Returns the smallest non-negative `x` with the combined modulus, or none if the
+/// congruences conflict or the combined modulus overflows.
+pub fn checked_crt<T: SignedInteger>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    crt_combine(congruences).ok().flatten()
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` pair, which need not have coprime moduli.
+///
+/// Returns the smallest non-negative `x` with the combined modulus, or none if the
+/// congruences conflict. Panics if the combined modulus overflows.
+pub fn crt<T: SignedInteger>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    crt_combine(congruences).expect("crt modulus overflowed")
+}
+
+// The outer result reports overflow, the inner option a conflict
+fn crt_combine<T: SignedInteger>(congruences: &[(T, T)]) -> Result<Option<(T, T)>, ()> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+
+    for &(r2, m2) in congruences {
+        let r2 = r2.rem_euclid(m2);
+        let (g, p, _) = extended_gcd(m, m2);
+        let diff = r2 - x;
+
+        if diff % g != T::ZERO {
+            return Ok(None);
+        }
+
+        // m * p ≡ g (mod m2), so stepping x by m * k lands on r2 for k = diff / g * p
+        let step = m2 / g;
+        let k = (diff / g).mul_mod(p, step);
+        let combined = (m / g).checked_mul(m2).ok_or(())?;
+
+        x = x.checked_add(m * k).ok_or(())?.rem_euclid(combined);
+        m = combined;
+    }
+
+    Ok(Some((x, m)))
+}
+
+/// Largest integer whose square is at most `n`. Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    n.isqrt()
+}
+
+/// Every prime up to and including `n`, by the sieve of Eratosthenes.
+pub fn sieve(n: usize) -> Vec<usize> {
+    let mut composite = vec![false; n + 1];
+    let mut primes = Vec::new();
+
+    for i in 2..=n {
+        if composite[i] {
+            continue;
+        }
+
+        primes.push(i);
+        for j in (i * i..=n).step_by(i) {
+            composite[j] = true;
+        }
+    }
+
+    primes
+}
+
+pub fn is_prime<T: Integer>(n: T) -> bool {
+    n > T::ONE && prime_factors(n) == [(n, 1)]
+}
+
+/// Prime factors of `n` with their multiplicities, smallest first, by trial division.
+///
+/// Empty for 1. Panics if `n` is less than 1.
+pub fn prime_factors<T: Integer>(n: T) -> Vec<(T, u32)> {
+    assert!(n >= T::ONE, "can only factor positive numbers");
+
+    let two = T::ONE + T::ONE;
+    let mut factors = Vec::new();
+    let mut n = n;
+    let mut p = two;
+
+    while p <= n / p {
+        let mut count = 0;
+        while n % p == T::ZERO {
+            n = n / p;
+            count += 1;
+        }
+        if count > 0 {
+            factors.push((p, count));
+        }
+        p = if p == two { p + T::ONE } else { p + two };
+    }
+
+    if n > T::ONE {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn brute_gcd(a: i64, b: i64) -> i64 {
+        (1..=a.abs().max(b.abs())).rev().find(|d| a % d == 0 && b % d == 0).unwrap_or(0)
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..500 {
+            let (a, b) = (rng.random_range(-300..300), rng.random_range(-300..300));
+            let g = gcd(a, b);
+            assert_eq!(g, brute_gcd(a, b), "gcd({}, {})", a, b);
+
+            let l = lcm(a, b);
+            let brute = match (a, b) {
+                (0, _) | (_, 0) => 0,
+                _ => (1..).map(|k| k * a.abs()).find(|m| m % b == 0).unwrap(),
+            };
+            assert_eq!(l, brute, "lcm({}, {})", a, b);
+        }
+
+        assert_eq!(gcd(0u8, 0u8), 0);
+        assert_eq!(gcd(12u128, 18u128), 6);
+    }
+
+    #[test]
+    fn test_lcm_overflow() {
+        // Multiplying first would overflow u64, dividing first does not
+        let a = 1u64 << 40;
+        assert_eq!(lcm(a, a * 2), a * 2);
+        assert_eq!(lcm_of(&[4u64, 6, 10]), 60);
+        assert_eq!(lcm_of::<u64>(&[]), 1);
+
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm_of(&[16u8, 3, 5]), Some(240));
+        assert_eq!(checked_lcm_of(&[16u8, 3, 7]), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let mut rng = StdRng::seed_from_u64(36);
+
+        for _ in 0..500 {
+            let (a, b) = (rng.random_range(-10_000i64..10_000), rng.random_range(-10_000i64..10_000));
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g, "extended_gcd({}, {})", a, b);
+        }
+
+        assert_eq!(extended_gcd(240i32, 46), (2, -9, 47));
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        for m in 1i32..60 {
+            for a in -60..60 {
+                let brute = (0..m).find(|&x| (a * x).rem_euclid(m) == 1 % m);
+                assert_eq!(mod_inverse(a, m), brute, "mod_inverse({}, {})", a, m);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let mut rng = StdRng::seed_from_u64(37);
+
+        for _ in 0..500 {
+            let (base, exp, m) = (rng.random_range(-50i64..50), rng.random_range(0u64..40), rng.random_range(1i64..1000));
+            let brute = (0..exp).fold(1 % m, |acc, _| (acc * base).rem_euclid(m));
+            assert_eq!(mod_pow(base, exp, m), brute, "mod_pow({}, {}, {})", base, exp, m);
+        }
+
+        // Moduli near the top of each width still multiply without overflowing
+        let p = (1u64 << 61) - 1;
+        assert_eq!(mod_pow(3u64, p - 1, p), 1);
+        let q = (1u128 << 127) - 1;
+        assert_eq!(mod_pow(2u128, 127, q), 1);
+        assert_eq!(mod_pow(q - 1, 3, q), q - 1);
+        assert_eq!(mod_pow(2u8, 10, 251), 20);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1i64, 4), (3, 6)]), Some((3, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+
+        let mut rng = StdRng::seed_from_u64(38);
+        for _ in 0..300 {
+            let congruences = (0..3).map(|_| {
+                let m = rng.random_range(1i64..20);
+                (rng.random_range(-50..50), m)
+            }).collect::<Vec<_>>();
+
+            let total = lcm_of(&congruences.iter().map(|c| c.1).collect::<Vec<_>>());
+            let brute = (0..total).find(|x| congruences.iter().all(|&(r, m)| (x - r).rem_euclid(m) == 0));
+            assert_eq!(crt(&congruences), brute.map(|x| (x, total)), "{:?}", congruences);
+        }
+
+        assert_eq!(checked_crt(&[(1i8, 11), (2, 13)]), None);
+        assert_eq!(checked_crt(&[(1i8, 7), (2, 11)]), Some((57, 77)));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0u32..5000 {
+            let brute = (0..).take_while(|r| r * r <= n).last().unwrap();
+            assert_eq!(isqrt(n), brute);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(99i8), 9);
+    }
+
+    #[test]
+    fn test_sieve() {
+        let primes = sieve(1000);
+        let brute = (2..=1000).filter(|&n| (2..n).all(|d| n % d != 0)).collect::<Vec<usize>>();
+        assert_eq!(primes, brute);
+        assert_eq!(sieve(1), Vec::<usize>::new());
+        assert!((0u64..1000).all(|n| is_prime(n) == primes.contains(&(n as usize))));
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        assert_eq!(prime_factors(360u32), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(prime_factors(1i64), vec![]);
+        assert_eq!(prime_factors(97u8), vec![(97, 1)]);
+        assert_eq!(prime_factors(600_851_475_143u64), vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]);
+
+        for n in 1u64..3000 {
+            let factors = prime_factors(n);
+            assert_eq!(factors.iter().map(|&(p, k)| p.pow(k)).product::<u64>(), n);
+            assert!(factors.iter().all(|&(p, _)| is_prime(p)));
+            assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+        }
+    }
+}
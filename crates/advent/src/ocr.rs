@@ -0,0 +1,254 @@
+//! Reads the block letters some puzzles draw their answers in, so those answers can be
+//! returned and tested as strings like any other. Both fonts the puzzles use are known:
+//! letters 6 pixels tall, about 4 wide, and letters 10 pixels tall, about 6 wide.
+//!
+//! Lit pixels are `#` in text and set bits in a `BitGrid`. Blank rows around the picture
+//! are ignored, and letters are told apart by the blank columns between them, or by the
+//! font's spacing where a wide letter such as `Y` runs into the next one.
+
+use crate::bitgrid::BitGrid;
+use std::fmt;
+
+// Each font drawn as a row of its letters, one or two blank columns apart
+const LETTERS_6: &str = "ABCEFGHIJKLOPRSUYZ";
+const FONT_6: &str = "\
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####
+";
+
+const LETTERS_10: &str = "ABCEFGHJKLNPRXZ";
+const FONT_10: &str = "\
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Nothing is lit.
+    Blank,
+    /// The lit rows span a height neither font has.
+    Height(usize),
+    /// Some letters matched nothing in the font. `text` has a `?` for each of them, and
+    /// `glyphs` has each one's position in the text and its pixels.
+    Unrecognised { text: String, glyphs: Vec<(usize, String)> },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Blank => write!(f, "no letters to read"),
+            OcrError::Height(h) => write!(f, "letters are {} pixels tall, not 6 or 10", h),
+            OcrError::Unrecognised { text, glyphs } => {
+                write!(f, "read {:?} with {} unknown letters", text, glyphs.len())?;
+                for (i, glyph) in glyphs {
+                    write!(f, "\nletter {}:\n{}", i + 1, glyph)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads text drawn with `#` for lit pixels, and anything else for unlit ones.
+pub fn read(s: &str) -> Result<String, OcrError> {
+    read_pixels(&pixels(s))
+}
+
+pub fn read_grid(grid: &BitGrid) -> Result<String, OcrError> {
+    let pixels = (0..grid.height())
+        .map(|y| (0..grid.width()).map(|x| grid.get(x as i64, y as i64)).collect())
+        .collect::<Vec<_>>();
+    read_pixels(&pixels)
+}
+
+fn read_pixels(pixels: &[Vec<bool>]) -> Result<String, OcrError> {
+    let glyphs = glyphs(pixels);
+    let Some(height) = glyphs.first().map(|g| g.len()) else {
+        return Err(OcrError::Blank);
+    };
+
+    // Letters sit 5 or 8 columns apart
+    let (font, pitch) = match height {
+        6 => (font(LETTERS_6, FONT_6), 5),
+        10 => (font(LETTERS_10, FONT_10), 8),
+        h => return Err(OcrError::Height(h)),
+    };
+    let widest = font.iter().map(|(_, g)| g[0].len()).max().unwrap();
+
+    let glyphs = glyphs.into_iter()
+        .flat_map(|g| if g[0].len() > widest { split(&g, pitch) } else { vec![g] })
+        .collect::<Vec<_>>();
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for (i, glyph) in glyphs.iter().enumerate() {
+        match font.iter().find(|(_, g)| g == glyph) {
+            Some(&(c, _)) => text.push(c),
+            None => {
+                text.push('?');
+                unknown.push((i, draw(glyph)));
+            }
+        }
+    }
+
+    match unknown.is_empty() {
+        true => Ok(text),
+        false => Err(OcrError::Unrecognised { text, glyphs: unknown }),
+    }
+}
+
+fn font(letters: &str, art: &str) -> Vec<(char, Vec<Vec<bool>>)> {
+    letters.chars().zip(glyphs(&pixels(art))).collect()
+}
+
+fn pixels(s: &str) -> Vec<Vec<bool>> {
+    s.lines().map(|line| line.chars().map(|c| c == '#').collect()).collect()
+}
+
+// Cuts the picture into letters at the blank columns, after dropping the blank rows above
+// and below, so every letter has the height of the whole picture
+fn glyphs(pixels: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+    let lit_rows = pixels.iter().enumerate().filter(|(_, row)| row.contains(&true)).map(|(y, _)| y).collect::<Vec<_>>();
+    let (Some(&top), Some(&bottom)) = (lit_rows.first(), lit_rows.last()) else { return Vec::new() };
+    let rows = &pixels[top..=bottom];
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut glyphs = Vec::new();
+    let mut start = None;
+    for x in 0..=width {
+        match (x < width && lit(x), start) {
+            (true, None) => start = Some(x),
+            (false, Some(s)) => {
+                glyphs.push(rows.iter().map(|row| (s..x).map(|x| row.get(x) == Some(&true)).collect()).collect());
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    glyphs
+}
+
+// Cuts letters that touch into pieces `pitch` columns wide, trimming each to its lit
+// columns
+fn split(glyph: &[Vec<bool>], pitch: usize) -> Vec<Vec<Vec<bool>>> {
+    let width = glyph[0].len();
+
+    (0..width).step_by(pitch)
+        .map(|start| start..(start + pitch).min(width))
+        .filter_map(|cols| {
+            let lit = cols.filter(|&x| glyph.iter().any(|row| row[x])).collect::<Vec<_>>();
+            let (&first, &last) = (lit.first()?, lit.last()?);
+            Some(glyph.iter().map(|row| row[first..=last].to_vec()).collect())
+        })
+        .collect()
+}
+
+fn draw(glyph: &[Vec<bool>]) -> String {
+    glyph.iter()
+        .map(|row| row.iter().map(|&p| if p { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fonts() {
+        assert_eq!(read(FONT_6), Ok(LETTERS_6.to_owned()));
+        assert_eq!(read(FONT_10), Ok(LETTERS_10.to_owned()));
+        assert_eq!(font(LETTERS_6, FONT_6).len(), LETTERS_6.len());
+        assert_eq!(font(LETTERS_10, FONT_10).len(), LETTERS_10.len());
+    }
+
+    #[test]
+    fn test_read_small() {
+        // Letters on a 5 column pitch, as the screens draw them, with blank rows around
+        let screen = "\
+..............................
+.##..###..#..#.####.###..####.
+#..#.#..#.#..#....#.#..#.#....
+#..#.#..#.####...#..#..#.###..
+####.###..#..#..#...###..#....
+#..#.#....#..#.#....#.#..#....
+#..#.#....#..#.####.#..#.####.
+..............................";
+        assert_eq!(read(screen), Ok("APHZRE".to_owned()));
+        assert_eq!(read(&screen.replace('.', " ")), Ok("APHZRE".to_owned()));
+    }
+
+    #[test]
+    fn test_read_large() {
+        let sky = "\
+#....#..######...####...#####
+#....#..#.......#....#..#....#
+#....#..#.......#.......#....#
+#....#..#.......#.......#....#
+######..#####...#.......#####.
+#....#..#.......#.......#.....
+#....#..#.......#.......#.....
+#....#..#.......#.......#.....
+#....#..#.......#....#..#.....
+#....#..######...####...#.....";
+        assert_eq!(read(sky), Ok("HECP".to_owned()));
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let art = pixels(FONT_6);
+        let grid = BitGrid::from_fn(art[0].len(), art.len(), |x, y| art[y][x]);
+        assert_eq!(read_grid(&grid), Ok(LETTERS_6.to_owned()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(read(""), Err(OcrError::Blank));
+        assert_eq!(read("....\n...."), Err(OcrError::Blank));
+        assert_eq!(read("#\n#\n#"), Err(OcrError::Height(3)));
+
+        // There is no M in the font
+        let screen = "\
+#..#.#...#.####
+#..#.##.##.#...
+####.#.#.#.###.
+#..#.#...#.#...
+#..#.#...#.#...
+#..#.#...#.####";
+        let err = read(screen).unwrap_err();
+        assert_eq!(err, OcrError::Unrecognised {
+            text: "H?E".to_owned(),
+            glyphs: vec![(1, "#...#\n##.##\n#.#.#\n#...#\n#...#\n#...#".to_owned())],
+        });
+        assert!(err.to_string().starts_with("read \"H?E\" with 1 unknown letters\nletter 2:\n#...#"));
+    }
+
+    #[test]
+    fn test_read_touching() {
+        // Y fills its 5 columns, leaving no blank column before the Z
+        let screen = "\
+#...#####.#..#
+#...#...#.#..#
+.#.#...#..####
+..#...#...#..#
+..#..#....#..#
+..#..####.#..#";
+        assert_eq!(read(screen), Ok("YZH".to_owned()));
+    }
+}
@@ -0,0 +1,96 @@
+//! Loops over independent items that run on a thread pool when the `parallel` feature is
+//! enabled, and as plain iterators otherwise. Results always come back in input order, so
+//! callers see the same answers either way.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Applies `f` to every item.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).collect();
+}
+
+/// Applies `f` to every item, keeping the results that are `Some`.
+pub fn filter_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().filter_map(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().filter_map(f).collect();
+}
+
+/// Applies `f` to every item and concatenates the results.
+pub fn flat_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Vec<R> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().flat_map_iter(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().flat_map(f).collect();
+}
+
+/// Sums `f` over every item.
+pub fn sum<T, F>(items: &[T], f: F) -> i64
+where
+    T: Sync,
+    F: Fn(&T) -> i64 + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).sum();
+
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).sum();
+}
+
+/// Whether the loops here run on a thread pool.
+pub fn enabled() -> bool {
+    cfg!(feature = "parallel")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collatz_steps(n: &u64) -> i64 {
+        let (mut n, mut steps) = (*n, 0);
+        while n != 1 {
+            n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn test_matches_sequential() {
+        let items = (1..5_000u64).collect::<Vec<_>>();
+
+        assert_eq!(map(&items, collatz_steps), items.iter().map(collatz_steps).collect::<Vec<_>>());
+        assert_eq!(sum(&items, collatz_steps), items.iter().map(collatz_steps).sum::<i64>());
+        assert_eq!(
+            filter_map(&items, |n| (n % 7 == 0).then(|| collatz_steps(n))),
+            items.iter().filter(|&n| n % 7 == 0).map(collatz_steps).collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            flat_map(&items, |&n| (0..n % 4).map(|k| n * 10 + k).collect()),
+            items.iter().flat_map(|&n| (0..n % 4).map(move |k| n * 10 + k)).collect::<Vec<_>>(),
+        );
+    }
+}
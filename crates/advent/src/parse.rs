@@ -0,0 +1,334 @@
+use nom::{IResult, Parser};
+use nom::character::complete::{char, digit1, line_ending, multispace0, one_of, space0};
+use nom::combinator::{all_consuming, map_res};
+use nom::error::ErrorKind;
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, separated_pair, terminated};
+use std::fmt;
+
+// Reusable nom parsers for puzzle inputs. Line breaks may be `\n` or `\r\n`.
+
+pub type NomError<'a> = nom::error::Error<&'a str>;
+
+/// Where and why a parser stopped short of the end of its input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+    pub found: String,
+}
+
+impl ParseError {
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        // A list stops before the line break ahead of a bad item, so report the item's line
+        let remaining = remaining.strip_prefix("\r\n").or(remaining.strip_prefix('\n')).unwrap_or(remaining);
+        let consumed = &input[..input.len() - remaining.len()];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        let found = remaining.lines().next().unwrap_or("").to_owned();
+
+        ParseError { line, column, kind, found }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = match &self.kind {
+            ErrorKind::Eof => "end of input",
+            ErrorKind::Count => "no further sections",
+            ErrorKind::Complete => "another section",
+            kind => kind.description(),
+        };
+        write!(f, "line {}, column {}: expected {}, found {:?}", self.line, self.column, expected, self.found)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs a parser over the whole input, allowing trailing whitespace.
+pub fn parse_input<'a, P>(input: &'a str, parser: P) -> Result<P::Output, ParseError>
+where
+    P: Parser<&'a str, Error = NomError<'a>>,
+{
+    match all_consuming(terminated(parser, multispace0)).parse(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(input, e.input, e.code)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(input, "", ErrorKind::Complete)),
+    }
+}
+
+/// A block of consecutive non-blank lines, and the line of the input it starts on.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    pub line: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Runs a parser over the whole section, reporting errors against the full input's lines.
+    pub fn parse<P>(&self, parser: P) -> Result<P::Output, ParseError>
+    where
+        P: Parser<&'a str, Error = NomError<'a>>,
+    {
+        parse_input(self.text, parser).map_err(|mut e| {
+            e.line += self.line - 1;
+            e
+        })
+    }
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn split_sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for (i, raw) in input.split_inclusive('\n').enumerate() {
+        let line = raw.trim_end_matches(['\r', '\n']);
+
+        if line.trim().is_empty() {
+            if let Some((from, at)) = start.take() {
+                sections.push(Section { text: &input[from..end], line: at });
+            }
+        } else {
+            start.get_or_insert((offset, i + 1));
+            end = offset + line.len();
+        }
+
+        offset += raw.len();
+    }
+
+    if let Some((from, at)) = start {
+        sections.push(Section { text: &input[from..end], line: at });
+    }
+
+    sections
+}
+
+/// Splits the input into exactly `N` sections, ready to destructure and parse one by one.
+pub fn split_sections_exact<const N: usize>(input: &str) -> Result<[Section<'_>; N], ParseError> {
+    let sections = split_sections(input);
+
+    match sections.get(N) {
+        Some(extra) => {
+            let offset = extra.text.as_ptr() as usize - input.as_ptr() as usize;
+            Err(ParseError::new(input, &input[offset..], ErrorKind::Count))
+        }
+        None => sections.try_into().map_err(|_| ParseError::new(input, "", ErrorKind::Complete)),
+    }
+}
+
+/// An optionally signed integer.
+pub fn int(s: &str) -> IResult<&str, i64> {
+    nom::character::complete::i64(s)
+}
+
+/// A run of decimal digits, parsed as an unsigned integer.
+pub fn uint(s: &str) -> IResult<&str, u64> {
+    map_res(digit1, str::parse::<u64>).parse(s)
+}
+
+/// An inclusive range written `a-b`.
+pub fn range(s: &str) -> IResult<&str, (i64, i64)> {
+    separated_pair(int, char('-'), int).parse(s)
+}
+
+fn comma(s: &str) -> IResult<&str, char> {
+    delimited(space0, char(','), space0).parse(s)
+}
+
+/// Two integers written `x,y`.
+pub fn int_pair(s: &str) -> IResult<&str, (i64, i64)> {
+    separated_pair(int, comma, int).parse(s)
+}
+
+/// Three integers written `x,y,z`.
+pub fn int_triple(s: &str) -> IResult<&str, (i64, i64, i64)> {
+    (int, comma, int, comma, int).map(|(x, _, y, _, z)| (x, y, z)).parse(s)
+}
+
+/// A direction letter from `directions` followed by a distance, such as `L10`.
+pub fn step<'a>(directions: &'static str) -> impl Parser<&'a str, Output = (char, i64), Error = NomError<'a>> {
+    pair(one_of(directions), int)
+}
+
+/// One or more items separated by commas.
+pub fn csv<'a, P>(item: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = NomError<'a>>
+where
+    P: Parser<&'a str, Error = NomError<'a>>,
+{
+    separated_list1(comma, item)
+}
+
+/// One item per line.
+pub fn lines<'a, P>(item: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = NomError<'a>>
+where
+    P: Parser<&'a str, Error = NomError<'a>>,
+{
+    separated_list1(line_ending, item)
+}
+
+/// An empty line between two sections.
+pub fn blank_line(s: &str) -> IResult<&str, (&str, &str)> {
+    (line_ending, line_ending).parse(s)
+}
+
+/// One or more sections separated by blank lines.
+pub fn sections<'a, P>(section: P) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = NomError<'a>>
+where
+    P: Parser<&'a str, Error = NomError<'a>>,
+{
+    separated_list1(blank_line, section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseInput;
+
+    #[derive(Debug, PartialEq, ParseInput)]
+    #[parse(delimiter = ",")]
+    struct Tile {
+        x: i64,
+        y: i64,
+    }
+
+    #[derive(Debug, PartialEq, ParseInput)]
+    struct Floor {
+        #[parse(lines)]
+        tiles: Vec<Tile>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Id(u64);
+
+    impl From<&str> for Id {
+        fn from(s: &str) -> Self {
+            Id(parse_input(s, uint).expect("Unable to parse id"))
+        }
+    }
+
+    #[derive(Debug, PartialEq, ParseInput)]
+    #[parse(sections)]
+    struct Manifest {
+        floor: Floor,
+        #[parse(lines)]
+        ids: Vec<Id>,
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse_input("-42", int), Ok(-42));
+        assert_eq!(parse_input("17", uint), Ok(17));
+        assert_eq!(parse_input("3-5", range), Ok((3, 5)));
+        assert_eq!(parse_input("-5--3", range), Ok((-5, -3)));
+        assert_eq!(parse_input("162,817", int_pair), Ok((162, 817)));
+        assert_eq!(parse_input("162, 817,-812", int_triple), Ok((162, 817, -812)));
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(parse_input("L68", step("LR")), Ok(('L', 68)));
+        assert_eq!(parse_input("R5", step("LR")), Ok(('R', 5)));
+        assert!(parse_input("U5", step("LR")).is_err());
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(parse_input("1-3,5-7,10-15\n", csv(range)), Ok(vec![(1, 3), (5, 7), (10, 15)]));
+        assert_eq!(parse_input("L10\r\nR5\nL3", lines(step("LR"))), Ok(vec![('L', 10), ('R', 5), ('L', 3)]));
+    }
+
+    #[test]
+    fn test_sections() {
+        let parsed = parse_input("1\r\n2\r\n\r\n3\r\n\r\n4\n5\n", sections(lines(int)));
+        assert_eq!(parsed, Ok(vec![vec![1, 2], vec![3], vec![4, 5]]));
+
+        let input = "3-5\r\n10-14\r\n\r\n1\r\n5";
+        let (ranges, ids) = parse_input(input, separated_pair(lines(range), blank_line, lines(int))).unwrap();
+        assert_eq!(ranges, vec![(3, 5), (10, 14)]);
+        assert_eq!(ids, vec![1, 5]);
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let input = "\n3-5\r\n10-14\r\n\r\n\r\n-1\r\n5\n\n";
+        let sections = split_sections(input);
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0], Section { text: "3-5\r\n10-14", line: 2 });
+        assert_eq!(sections[1], Section { text: "-1\r\n5", line: 6 });
+        assert_eq!(sections[1].parse(lines(int)), Ok(vec![-1, 5]));
+        assert!(split_sections("\r\n\r\n").is_empty());
+    }
+
+    #[test]
+    fn test_split_sections_exact() {
+        let [ranges, ids] = split_sections_exact("3-5\n10-14\n\n1\n5").unwrap();
+        assert_eq!(ranges.parse(lines(range)), Ok(vec![(3, 5), (10, 14)]));
+        assert_eq!(ids.parse(lines(int)), Ok(vec![1, 5]));
+
+        let [_, ids] = split_sections_exact("3-5\n\n\n1\n2x").unwrap();
+        let err = ids.parse(lines(int)).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 2));
+        assert_eq!(err.found, "x");
+
+        let err = split_sections_exact::<2>("1\n\n2\n\n3\n4").unwrap_err();
+        assert_eq!(err.to_string(), "line 5, column 1: expected no further sections, found \"3\"");
+
+        let err = split_sections_exact::<2>("1\n2").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: expected another section, found \"\"");
+    }
+
+    #[test]
+    fn test_derive_delimited() {
+        assert_eq!(Tile::from(" 7, -1\r\n"), Tile { x: 7, y: -1 });
+    }
+
+    #[test]
+    fn test_derive_lines() {
+        let floor = Floor::from("7,1\r\n11,1\n\n11,7\n");
+        assert_eq!(floor.tiles, vec![Tile { x: 7, y: 1 }, Tile { x: 11, y: 1 }, Tile { x: 11, y: 7 }]);
+    }
+
+    #[test]
+    fn test_derive_sections() {
+        let manifest = Manifest::from("1,2\r\n3,4\r\n\r\n10\r\n20");
+        assert_eq!(manifest.floor.tiles, vec![Tile { x: 1, y: 2 }, Tile { x: 3, y: 4 }]);
+        assert_eq!(manifest.ids, vec![Id(10), Id(20)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many fields for Tile")]
+    fn test_derive_extra_field() {
+        let _ = Tile::from("1,2,3");
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 2 sections for Manifest")]
+    fn test_derive_missing_section() {
+        let _ = Manifest::from("1,2\n3,4");
+    }
+
+    #[test]
+    fn test_located_error() {
+        let err = parse_input("L10\nR5\nL3x\nR2", lines(step("LR"))).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.found, "x");
+        assert_eq!(err.to_string(), "line 3, column 3: expected end of input, found \"x\"");
+
+        let err = parse_input("L10\r\nX5\r\nL3", lines(step("LR"))).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "X5");
+
+        let err = parse_input("12,x", int_pair).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 4: expected Digit, found \"x\"");
+
+        let err = parse_input("1-3,5-x", csv(range)).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.found, ",5-x");
+    }
+}
@@ -0,0 +1,517 @@
+use crate::Point64;
+use crate::compress::{Compressor, PrefixSum2D};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+// Geometry over integer tiles. A polygon's boundary runs through tile centres, and a
+// tile counts as inside when it is on the boundary or enclosed by it. Rectangles are
+// likewise measured in whole tiles, so both corner tiles are included.
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Rect {
+    pub min: Point64,
+    pub max: Point64,
+}
+
+impl Rect {
+    pub fn from_corners(a: Point64, b: Point64) -> Self {
+        Rect {
+            min: Point64::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point64::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn width(&self) -> i64 { self.max.x - self.min.x + 1 }
+    pub fn height(&self) -> i64 { self.max.y - self.min.y + 1 }
+    pub fn area(&self) -> i64 { self.width() * self.height() }
+
+    pub fn contains(&self, p: Point64) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn contains_strictly(&self, p: Point64) -> bool {
+        self.min.x < p.x && p.x < self.max.x && self.min.y < p.y && p.y < self.max.y
+    }
+
+    pub fn overlaps(&self, r: &Rect) -> bool {
+        self.min.x <= r.max.x && r.min.x <= self.max.x && self.min.y <= r.max.y && r.min.y <= self.max.y
+    }
+}
+
+/// An axis-aligned segment between two points.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Segment {
+    pub a: Point64,
+    pub b: Point64,
+}
+
+impl Segment {
+    pub fn new(a: Point64, b: Point64) -> Self {
+        Segment { a, b }
+    }
+
+    pub fn is_horizontal(&self) -> bool { self.a.y == self.b.y }
+    pub fn is_vertical(&self) -> bool { self.a.x == self.b.x }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::from_corners(self.a, self.b)
+    }
+
+    pub fn contains(&self, p: Point64) -> bool {
+        self.bounds().contains(p)
+    }
+
+    /// Whether any point of the segment lies in the rectangle, edges included.
+    pub fn intersects(&self, r: &Rect) -> bool {
+        self.bounds().overlaps(r)
+    }
+
+    /// Whether the segment passes through the open interior of the rectangle.
+    pub fn crosses_interior(&self, r: &Rect) -> bool {
+        let b = self.bounds();
+        b.min.x < r.max.x && r.min.x < b.max.x && b.min.y < r.max.y && r.min.y < b.max.y
+    }
+}
+
+/// A closed polygon whose consecutive vertices share a row or a column.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point64>,
+    edges: Vec<Segment>,
+    coverage: OnceLock<Coverage>,
+}
+
+/// Winding direction as drawn on a puzzle grid, with y increasing downwards.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    DiagonalEdge { edge: usize, segment: Segment },
+    DuplicateVertex { first: usize, second: usize, vertex: Point64 },
+    SelfIntersection { first: usize, second: usize },
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(n) => write!(f, "polygon has {} vertices, at least 4 are needed", n),
+            PolygonError::DiagonalEdge { edge, segment } => write!(f, "edge {} from {},{} to {},{} is not axis-aligned",
+                edge, segment.a.x, segment.a.y, segment.b.x, segment.b.y),
+            PolygonError::DuplicateVertex { first, second, vertex } => write!(f, "vertices {} and {} are both {},{}",
+                first, second, vertex.x, vertex.y),
+            PolygonError::SelfIntersection { first, second } => write!(f, "edges {} and {} intersect", first, second),
+        }
+    }
+}
+
+impl std::error::Error for PolygonError {}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point64>) -> Self {
+        let edges = vertices.iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| Segment::new(*a, *b))
+            .collect::<Vec<_>>();
+
+        Polygon { vertices, edges, coverage: OnceLock::new() }
+    }
+
+    pub fn vertices(&self) -> &[Point64] { &self.vertices }
+    pub fn edges(&self) -> &[Segment] { &self.edges }
+
+    pub fn on_boundary(&self, p: Point64) -> bool {
+        self.edges.iter().any(|e| e.contains(p))
+    }
+
+    /// Point-in-polygon, with points on the boundary counted as inside.
+    pub fn contains(&self, p: Point64) -> bool {
+        // Cast a ray to the left, counting each vertical edge at the bottom end only
+        let crossings = self.edges.iter()
+            .filter(|e| e.is_vertical() && e.a.x < p.x)
+            .filter(|e| e.a.y.min(e.b.y) <= p.y && p.y < e.a.y.max(e.b.y))
+            .count();
+
+        crossings % 2 == 1 || self.on_boundary(p)
+    }
+
+    /// Whether every tile of the rectangle is inside the polygon.
+    pub fn contains_rect(&self, r: &Rect) -> bool {
+        self.coverage().contains_rect(r)
+    }
+
+    /// Twice the signed area, positive when the vertices run clockwise.
+    pub fn signed_area2(&self) -> i64 {
+        self.edges.iter().map(|e| e.a.x * e.b.y - e.b.x * e.a.y).sum()
+    }
+
+    pub fn orientation(&self) -> Option<Orientation> {
+        match self.signed_area2() {
+            a if a > 0 => Some(Orientation::Clockwise),
+            a if a < 0 => Some(Orientation::CounterClockwise),
+            _ => None,
+        }
+    }
+
+    /// Checks the vertices describe a simple rectilinear loop, returning its orientation or
+    /// every problem found.
+    pub fn validate(&self) -> Result<Orientation, Vec<PolygonError>> {
+        let n = self.vertices.len();
+        if n < 4 {
+            return Err(vec![PolygonError::TooFewVertices(n)]);
+        }
+
+        let mut errors = Vec::new();
+
+        for (i, e) in self.edges.iter().enumerate() {
+            if !e.is_horizontal() && !e.is_vertical() {
+                errors.push(PolygonError::DiagonalEdge { edge: i, segment: *e });
+            }
+        }
+
+        let mut seen: HashMap<Point64, usize> = HashMap::new();
+        for (i, v) in self.vertices.iter().enumerate() {
+            if let Some(&first) = seen.get(v) {
+                errors.push(PolygonError::DuplicateVertex { first, second: i, vertex: *v });
+            } else {
+                seen.insert(*v, i);
+            }
+        }
+
+        // Only meaningful once every edge is axis-aligned
+        if errors.iter().all(|e| !matches!(e, PolygonError::DiagonalEdge { .. })) {
+            for i in 0..n {
+                for j in i + 1..n {
+                    if self.edges_intersect(i, j) {
+                        errors.push(PolygonError::SelfIntersection { first: i, second: j });
+                    }
+                }
+            }
+        }
+
+        match (errors.is_empty(), self.orientation()) {
+            (true, Some(orientation)) => Ok(orientation),
+            _ => Err(errors),
+        }
+    }
+
+    // Neighbouring edges may only share their common vertex, others may not touch at all
+    fn edges_intersect(&self, i: usize, j: usize) -> bool {
+        let n = self.edges.len();
+        let (e1, e2) = (self.edges[i], self.edges[j]);
+
+        if j == i + 1 || (i == 0 && j == n - 1) {
+            let (first, second) = if j == i + 1 { (e1, e2) } else { (e2, e1) };
+            let d1 = (first.b.x - first.a.x, first.b.y - first.a.y);
+            let d2 = (second.b.x - second.a.x, second.b.y - second.a.y);
+
+            // Doubling back along the same line
+            return d1.0 * d2.0 < 0 || d1.1 * d2.1 < 0;
+        }
+
+        e1.intersects(&e2.bounds())
+    }
+
+    /// The polygon's coverage, built on first use and kept for later queries.
+    pub fn coverage(&self) -> &Coverage {
+        self.coverage.get_or_init(|| Coverage::new(self))
+    }
+
+    /// The largest rectangle inside the polygon with opposite corners on two vertices.
+    pub fn largest_rect(&self) -> Option<Rect> {
+        let coverage = self.coverage();
+        let mut best: Option<Rect> = None;
+
+        for (i, a) in self.vertices.iter().enumerate() {
+            for b in self.vertices.iter().skip(i + 1) {
+                let r = Rect::from_corners(*a, *b);
+                if best.is_none_or(|br| r.area() > br.area()) && coverage.contains_rect(&r) {
+                    best = Some(r);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/// Which tiles of a polygon's bounding box lie outside it, for constant time rectangle queries.
+///
+/// The vertex coordinates are compressed into cells, each of which is wholly inside or wholly
+/// outside the polygon, and a prefix sum over the outside cells answers each query.
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    xs: Compressor,
+    ys: Compressor,
+    outside: PrefixSum2D,
+}
+
+impl Coverage {
+    pub fn new(poly: &Polygon) -> Self {
+        let xs = Compressor::new(poly.vertices.iter().map(|v| v.x));
+        let ys = Compressor::new(poly.vertices.iter().map(|v| v.y));
+
+        let rows = (0..ys.cell_count())
+            .map(|cy| ys.cell_span(cy).map(|(y, _)| Coverage::inside_spans(poly, y)))
+            .collect::<Vec<_>>();
+
+        let outside = PrefixSum2D::new(xs.cell_count(), ys.cell_count(), |cx, cy| {
+            match (xs.cell_span(cx), &rows[cy]) {
+                (Some((x, _)), Some(spans)) => !spans.iter().any(|&(x1, x2)| x1 <= x && x <= x2) as i64,
+                _ => 0,
+            }
+        });
+
+        Coverage { xs, ys, outside }
+    }
+
+    /// Whether every tile of the rectangle is inside the polygon.
+    pub fn contains_rect(&self, r: &Rect) -> bool {
+        let (Some(x1), Some(x2)) = (self.xs.cell(r.min.x), self.xs.cell(r.max.x)) else { return false };
+        let (Some(y1), Some(y2)) = (self.ys.cell(r.min.y), self.ys.cell(r.max.y)) else { return false };
+
+        self.outside.sum(x1, y1, x2, y2) == 0
+    }
+
+    // Closed x ranges of the tiles on row `y` that are inside or on the boundary
+    fn inside_spans(poly: &Polygon, y: i64) -> Vec<(i64, i64)> {
+        let mut crossings = poly.edges.iter()
+            .filter(|e| e.is_vertical() && e.a.y.min(e.b.y) <= y && y < e.a.y.max(e.b.y))
+            .map(|e| e.a.x)
+            .collect::<Vec<_>>();
+        crossings.sort_unstable();
+
+        let mut spans = crossings.chunks(2)
+            .filter(|c| c.len() == 2)
+            .map(|c| (c[0], c[1]))
+            .collect::<Vec<_>>();
+
+        spans.extend(poly.edges.iter()
+            .filter(|e| e.is_horizontal() && e.a.y == y)
+            .map(|e| (e.a.x.min(e.b.x), e.a.x.max(e.b.x))));
+
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::rectilinear_loop;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::{HashSet, VecDeque};
+
+    fn polygon(points: &[(i64, i64)]) -> Polygon {
+        Polygon::new(points.iter().map(|&(x, y)| Point64::new(x, y)).collect())
+    }
+
+    fn sample_polygon() -> Polygon {
+        polygon(&[(7, 1), (11, 1), (11, 7), (9, 7), (9, 5), (2, 5), (2, 3), (7, 3)])
+    }
+
+    #[test]
+    fn test_rect() {
+        let r = Rect::from_corners(Point64::new(9, 5), Point64::new(2, 3));
+        assert_eq!(r.min, Point64::new(2, 3));
+        assert_eq!(r.max, Point64::new(9, 5));
+        assert_eq!(r.area(), 24);
+        assert!(r.contains(Point64::new(2, 4)));
+        assert!(!r.contains_strictly(Point64::new(2, 4)));
+        assert!(r.contains_strictly(Point64::new(3, 4)));
+    }
+
+    #[test]
+    fn test_segment_rect() {
+        let r = Rect::from_corners(Point64::new(2, 2), Point64::new(6, 6));
+        let through = Segment::new(Point64::new(4, 0), Point64::new(4, 9));
+        let along = Segment::new(Point64::new(2, 0), Point64::new(2, 9));
+        let touching = Segment::new(Point64::new(0, 6), Point64::new(2, 6));
+        let clear = Segment::new(Point64::new(7, 0), Point64::new(7, 9));
+
+        assert!(through.intersects(&r) && through.crosses_interior(&r));
+        assert!(along.intersects(&r) && !along.crosses_interior(&r));
+        assert!(touching.intersects(&r) && !touching.crosses_interior(&r));
+        assert!(!clear.intersects(&r) && !clear.crosses_interior(&r));
+    }
+
+    #[test]
+    fn test_contains() {
+        let poly = sample_polygon();
+        assert!(poly.contains(Point64::new(7, 1)));
+        assert!(poly.contains(Point64::new(9, 1)));
+        assert!(poly.contains(Point64::new(10, 6)));
+        assert!(poly.contains(Point64::new(3, 4)));
+        assert!(!poly.contains(Point64::new(3, 2)));
+        assert!(!poly.contains(Point64::new(8, 6)));
+        assert!(!poly.contains(Point64::new(12, 3)));
+        assert!(poly.on_boundary(Point64::new(5, 5)));
+        assert!(!poly.on_boundary(Point64::new(5, 4)));
+    }
+
+    #[test]
+    fn test_orientation() {
+        let poly = sample_polygon();
+        assert_eq!(poly.signed_area2(), 2 * 30);
+        assert_eq!(poly.validate(), Ok(Orientation::Clockwise));
+
+        let reversed = Polygon::new(poly.vertices().iter().rev().copied().collect());
+        assert_eq!(reversed.validate(), Ok(Orientation::CounterClockwise));
+    }
+
+    #[test]
+    fn test_validate_errors() {
+        let few = polygon(&[(0, 0), (4, 0), (4, 4)]);
+        assert_eq!(few.validate(), Err(vec![PolygonError::TooFewVertices(3)]));
+
+        let diagonal = polygon(&[(0, 0), (4, 0), (4, 4), (1, 3)]);
+        assert_eq!(diagonal.validate(), Err(vec![
+            PolygonError::DiagonalEdge { edge: 2, segment: Segment::new(Point64::new(4, 4), Point64::new(1, 3)) },
+            PolygonError::DiagonalEdge { edge: 3, segment: Segment::new(Point64::new(1, 3), Point64::new(0, 0)) },
+        ]));
+
+        // A figure of eight crossing itself at 2,2
+        let crossed = polygon(&[(0, 0), (2, 0), (2, 4), (4, 4), (4, 2), (0, 2)]);
+        assert_eq!(crossed.validate(), Err(vec![PolygonError::SelfIntersection { first: 1, second: 4 }]));
+
+        // Two squares meeting at the corner 2,2, which is visited twice
+        let repeated = polygon(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (2, 4), (2, 2), (0, 2)]);
+        assert_eq!(repeated.validate(), Err(vec![
+            PolygonError::DuplicateVertex { first: 2, second: 6, vertex: Point64::new(2, 2) },
+            PolygonError::SelfIntersection { first: 1, second: 5 },
+            PolygonError::SelfIntersection { first: 1, second: 6 },
+            PolygonError::SelfIntersection { first: 2, second: 5 },
+            PolygonError::SelfIntersection { first: 2, second: 6 },
+        ]));
+    }
+
+    #[test]
+    fn test_validate_spike() {
+        // Runs out along y=0 to 6,0 and straight back to 4,0, where the next edge leaves the first
+        let spike = polygon(&[(0, 0), (6, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(spike.validate(), Err(vec![
+            PolygonError::SelfIntersection { first: 0, second: 1 },
+            PolygonError::SelfIntersection { first: 0, second: 2 },
+        ]));
+
+        // Collinear vertices are fine
+        let collinear = polygon(&[(0, 0), (2, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(collinear.validate(), Ok(Orientation::Clockwise));
+    }
+
+    #[test]
+    fn test_largest_rect() {
+        let r = sample_polygon().largest_rect().unwrap();
+        assert_eq!(r, Rect::from_corners(Point64::new(9, 5), Point64::new(2, 3)));
+        assert_eq!(r.area(), 24);
+
+        let b = polygon(&[(5, 1), (5, 7), (2, 7), (2, 5), (4, 5), (4, 3), (2, 3), (2, 1)]);
+        assert_eq!(b.largest_rect().map(|r| r.area()), Some(12));
+    }
+
+    #[test]
+    fn test_largest_rect_narrow_notch() {
+        // The notch between x=2 and x=3 holds no tiles, so it does not break the rectangle
+        let poly = polygon(&[(0, 0), (2, 0), (2, 4), (3, 4), (3, 0), (5, 0), (5, 6), (0, 6)]);
+        assert!(poly.contains_rect(&Rect::from_corners(Point64::new(0, 0), Point64::new(5, 6))));
+        assert_eq!(poly.largest_rect().map(|r| r.area()), Some(42));
+
+        let wide = polygon(&[(0, 0), (2, 0), (2, 4), (4, 4), (4, 0), (6, 0), (6, 6), (0, 6)]);
+        let coverage = wide.coverage();
+        assert!(!coverage.contains_rect(&Rect::from_corners(Point64::new(0, 0), Point64::new(6, 6))));
+        assert!(!coverage.contains_rect(&Rect::from_corners(Point64::new(3, 3), Point64::new(3, 3))));
+        assert!(!coverage.contains_rect(&Rect::from_corners(Point64::new(5, 5), Point64::new(7, 5))));
+        assert!(coverage.contains_rect(&Rect::from_corners(Point64::new(1, 4), Point64::new(5, 5))));
+        assert!(coverage.contains_rect(&Rect::from_corners(Point64::new(5, 1), Point64::new(6, 3))));
+        assert_eq!(wide.largest_rect().map(|r| r.area()), Some(21));
+        assert!(std::ptr::eq(coverage, wide.coverage()));
+    }
+
+    // Fills the outside of the polygon on a grid of half tiles, so the fill can squeeze
+    // between boundary tiles that touch, and returns the tiles left over along with the
+    // largest rectangle found by testing every vertex pair against them
+    fn oracle(poly: &Polygon) -> (HashSet<Point64>, i64) {
+        let min_x = poly.vertices().iter().map(|v| v.x).min().unwrap() - 1;
+        let max_x = poly.vertices().iter().map(|v| v.x).max().unwrap() + 1;
+        let min_y = poly.vertices().iter().map(|v| v.y).min().unwrap() - 1;
+        let max_y = poly.vertices().iter().map(|v| v.y).max().unwrap() + 1;
+
+        let mut boundary = HashSet::new();
+        for e in poly.edges() {
+            let b = e.bounds();
+            for y in b.min.y * 2..=b.max.y * 2 {
+                for x in b.min.x * 2..=b.max.x * 2 {
+                    boundary.insert(Point64::new(x, y));
+                }
+            }
+        }
+
+        let start = Point64::new(min_x * 2, min_y * 2);
+        let mut outside = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(p) = queue.pop_front() {
+            for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+                let n = Point64::new(p.x + dx, p.y + dy);
+                if (min_x * 2..=max_x * 2).contains(&n.x) && (min_y * 2..=max_y * 2).contains(&n.y) && !boundary.contains(&n) && outside.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        let inside = (min_y..=max_y)
+            .flat_map(|y| (min_x..=max_x).map(move |x| Point64::new(x, y)))
+            .filter(|p| !outside.contains(&Point64::new(p.x * 2, p.y * 2)))
+            .collect::<HashSet<_>>();
+
+        let mut best = 0;
+        for a in poly.vertices() {
+            for b in poly.vertices() {
+                let r = Rect::from_corners(*a, *b);
+                let tiles = (r.min.y..=r.max.y).flat_map(|y| (r.min.x..=r.max.x).map(move |x| Point64::new(x, y)));
+                if r.area() > best && tiles.into_iter().all(|p| inside.contains(&p)) {
+                    best = r.area();
+                }
+            }
+        }
+
+        (inside, best)
+    }
+
+    #[test]
+    fn test_touching_boundary_notch() {
+        // The notch at (6..7, 5..6) is outside, though boundary tiles wall it off from the rest
+        let poly = polygon(&[(4, 3), (11, 3), (11, 4), (14, 4), (14, 6), (16, 6), (16, 8), (11, 8),
+            (11, 9), (5, 9), (5, 7), (8, 7), (8, 4), (5, 4), (5, 6), (4, 6)]);
+        let (inside, best) = oracle(&poly);
+
+        assert!(!poly.contains(Point64::new(6, 5)));
+        assert!(!inside.contains(&Point64::new(6, 5)));
+        assert_eq!(poly.largest_rect().map(|r| r.area()), Some(28));
+        assert_eq!(best, 28);
+    }
+
+    #[test]
+    fn test_against_raster_oracle() {
+        let mut rng = StdRng::seed_from_u64(9);
+
+        for _ in 0..150 {
+            let poly = Polygon::new(rectilinear_loop(&mut rng, 6));
+            let (inside, best) = oracle(&poly);
+
+            assert!(poly.validate().is_ok(), "{:?}", poly.vertices());
+            assert_eq!(poly.largest_rect().map(|r| r.area()), Some(best), "{:?}", poly.vertices());
+
+            for y in 0..25 {
+                for x in 0..25 {
+                    let p = Point64::new(x, y);
+                    assert_eq!(poly.contains(p), inside.contains(&p), "{:?} {:?}", p, poly.vertices());
+                }
+            }
+        }
+    }
+}
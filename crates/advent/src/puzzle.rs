@@ -0,0 +1,110 @@
+/// A puzzle description stored as Markdown by `aoc fetch`, for tests that want the
+/// examples from the puzzle text rather than a copy pasted into the test.
+///
+/// Each part starts with a `## ` heading, examples are fenced with three backticks and
+/// highlighted text is in bold.
+#[derive(Debug, Clone)]
+pub struct Description<'a> {
+    parts: Vec<&'a str>,
+}
+
+impl<'a> From<&'a str> for Description<'a> {
+    fn from(s: &'a str) -> Self {
+        let parts = s.strip_prefix("## ").unwrap_or(s)
+            .split("\n## ")
+            .filter(|p| !p.trim().is_empty())
+            .collect();
+        Description { parts }
+    }
+}
+
+impl<'a> Description<'a> {
+    /// How many parts the description covers, which is 1 until part 1 has been solved.
+    pub fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// The text of every example block in a part, counting parts from 1.
+    pub fn examples(&self, part: usize) -> Vec<&'a str> {
+        let Some(text) = self.part(part) else { return Vec::new() };
+
+        // Splitting on the fences leaves prose and examples alternating
+        text.split("```\n")
+            .skip(1)
+            .step_by(2)
+            .collect()
+    }
+
+    /// The highlighted text in a part, outside the examples, counting parts from 1.
+    pub fn highlights(&self, part: usize) -> Vec<&'a str> {
+        let Some(text) = self.part(part) else { return Vec::new() };
+
+        text.split("```\n")
+            .step_by(2)
+            .flat_map(|prose| prose.split("**").skip(1).step_by(2))
+            .map(|h| h.trim_matches('`'))
+            .collect()
+    }
+
+    fn part(&self, part: usize) -> Option<&'a str> {
+        self.parts.get(part.wrapping_sub(1)).copied()
+    }
+}
+
+/// Includes the Markdown description of a day from the `problem` directory of the year
+/// crate.
+#[macro_export]
+macro_rules! include_problem {
+    ($day:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/problem/", stringify!($day), ".md"))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = "\
+## --- Day 7: Laboratories ---
+
+The beam enters at `S`:
+
+```
+..S..
+.....
+..^..
+```
+
+- A beam that reaches a splitter **stops**.
+
+In total, the beam is split **`21`** times.
+
+## --- Part Two ---
+
+Count timelines instead:
+
+```
+..S..
+```
+
+The example has **`40`** timelines.
+";
+
+    #[test]
+    fn test_examples() {
+        let d = Description::from(DESCRIPTION);
+
+        assert_eq!(d.part_count(), 2);
+        assert_eq!(d.examples(1), vec!["..S..\n.....\n..^..\n"]);
+        assert_eq!(d.examples(2), vec!["..S..\n"]);
+        assert!(d.examples(3).is_empty());
+    }
+
+    #[test]
+    fn test_highlights() {
+        let d = Description::from(DESCRIPTION);
+
+        assert_eq!(d.highlights(1), vec!["stops", "21"]);
+        assert_eq!(d.highlights(2), vec!["40"]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Running days outside of tests: reading puzzle inputs from the directories in
+//! `aoc.toml` and timing the parts.
+//!
+//! A day can have any number of inputs. `NN.txt` is the puzzle input, and `NN<variant>.txt`
+//! files such as `07a.txt` are others, like the examples from the puzzle text. Their
+//! answers can be recorded in `expected.toml` in the answer directory, as `[07a]` tables
+//! with `part1` and `part2`, for tests to check.
+
+use crate::config::{Config, LogLevel};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+pub const EXPECTED_FILE: &str = "expected.toml";
+
+/// A day's number and the solvers for its two parts, each given the puzzle input, along
+/// with a way to draw its working for days that have one.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u32,
+    pub part1: fn(&str) -> String,
+    pub part2: fn(&str) -> String,
+    pub visualize: Option<fn(&str)>,
+}
+
+impl Day {
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    /// Where the day's input is kept: `NN.txt`, or `NNa.txt` and so on for a variant.
+    pub fn input_path(&self, config: &Config, year: u32, variant: Option<&str>) -> PathBuf {
+        config.input_dir(year).join(format!("{:02}{}.txt", self.day, variant.unwrap_or("")))
+    }
+
+    /// The input for a year, as chosen for the day in `aoc.toml`.
+    pub fn read_input(&self, config: &Config, year: u32) -> Result<String, String> {
+        self.read_variant(config, year, config.day(self.day).input.as_deref())
+    }
+
+    pub fn read_variant(&self, config: &Config, year: u32, variant: Option<&str>) -> Result<String, String> {
+        let path = self.input_path(config, year, variant);
+        read_input_file(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Draws the day's working for an input, if the settings ask for it and the day can.
+    pub fn visualize(&self, config: &Config, input: &str) {
+        if let Some(visualize) = self.visualize.filter(|_| config.visualize(self.day)) {
+            visualize(input);
+        }
+    }
+
+    /// Both answers, with how long each part took.
+    pub fn solve(&self, input: &str) -> [(String, Duration); 2] {
+        [self.part1, self.part2].map(|part| {
+            let start = Instant::now();
+            let answer = part(input);
+            (answer, start.elapsed())
+        })
+    }
+}
+
+/// Reads an input with CRLF line endings, like the inputs written into tests, whatever the
+/// checkout used.
+pub fn read_input_file(path: &Path) -> std::io::Result<String> {
+    Ok(fs::read_to_string(path)?.replace("\r\n", "\n").replace('\n', "\r\n"))
+}
+
+/// An input file of a day, named by the day and the variant, which is empty for the
+/// puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Variant {
+    pub day: u32,
+    pub name: String,
+}
+
+impl Variant {
+    /// The file name without `.txt`, such as `07a`.
+    pub fn stem(&self) -> String {
+        format!("{:02}{}", self.day, self.name)
+    }
+
+    fn from_file_name(name: &str) -> Option<Self> {
+        let stem = name.strip_suffix(".txt")?;
+        let (day, name) = stem.split_at_checked(2)?;
+        if !day.bytes().all(|b| b.is_ascii_digit()) || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+            return None;
+        }
+        Some(Variant { day: day.parse().ok()?, name: name.to_owned() })
+    }
+}
+
+/// Every input in a directory, by day and then variant.
+pub fn variants(dir: &Path) -> Result<Vec<Variant>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let mut variants = entries
+        .filter_map(|e| Variant::from_file_name(e.ok()?.file_name().to_str()?))
+        .collect::<Vec<_>>();
+    variants.sort();
+    Ok(variants)
+}
+
+/// The recorded answers for an input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// The answers in an `expected.toml`, by input stem, or none if there is no file.
+pub fn load_expected(path: &Path) -> Result<BTreeMap<String, Expected>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// Solves every input in `input_dir` that has answers in `expected`, and describes each
+/// answer that differs. Recorded answers for inputs that are missing count as differences.
+pub fn check_expected(days: &[Day], input_dir: &Path, expected: &BTreeMap<String, Expected>) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    for (stem, answers) in expected {
+        let Some(v) = Variant::from_file_name(&format!("{}.txt", stem)) else {
+            failures.push(format!("{}: not an input name", stem));
+            continue;
+        };
+        let Some(day) = days.iter().find(|d| d.day == v.day) else {
+            failures.push(format!("{}: no solution for day {}", stem, v.day));
+            continue;
+        };
+        let input = match read_input_file(&input_dir.join(format!("{}.txt", stem))) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: {}", stem, e));
+                continue;
+            }
+        };
+
+        for (n, part, want) in [(1, day.part1, &answers.part1), (2, day.part2, &answers.part2)] {
+            if let Some(want) = want {
+                let got = part(&input);
+                if &got != want {
+                    failures.push(format!("{} part {}: expected {}, got {}", stem, n, want, got));
+                }
+            }
+        }
+    }
+
+    failures
+}
+
+/// The `main` of a day binary: applies the command line to the settings, solves the day
+/// and prints the two answers. `--input <variant>` picks an input other than the one in
+/// the settings, and `--list` shows the day's inputs instead.
+pub fn main(year: u32, days: &[Day], day: u32) -> ExitCode {
+    let day = days.iter().find(|d| d.day == day).expect("Day is not in the year's list");
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let (config, rest) = match Config::load().and_then(|mut c| c.apply_args(&args).map(|rest| (c, rest))) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let variant = match &rest[..] {
+        [] => config.day(day.day).input,
+        [flag, variant] if flag == "--input" => Some(variant.clone()),
+        [flag] if flag == "--list" => return list(&config, year, Some(day.day)),
+        _ => {
+            eprintln!("unexpected arguments: {}", rest.join(" "));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input = match day.read_variant(&config, year, variant.as_deref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let log = config.log_level(day.day);
+    if log >= LogLevel::Debug {
+        eprintln!("{} {}: {} lines of input", year, day.name(), input.lines().count());
+    }
+    day.visualize(&config, &input);
+
+    for (part, (answer, elapsed)) in day.solve(&input).iter().enumerate() {
+        println!("{}", answer);
+        if log >= LogLevel::Info {
+            eprintln!("part {} took {:?}", part + 1, elapsed);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Prints the inputs of one day, or of every day, marking the ones the settings pick and
+/// the ones with recorded answers.
+pub fn list(config: &Config, year: u32, day: Option<u32>) -> ExitCode {
+    let listed = variants(&config.input_dir(year))
+        .and_then(|v| Ok((v, load_expected(&config.answer_dir(year).join(EXPECTED_FILE))?)));
+    let (variants, expected) = match listed {
+        Ok(listed) => listed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for v in variants.iter().filter(|v| day.is_none_or(|d| d == v.day)) {
+        let chosen = config.day(v.day).input.unwrap_or_default() == v.name;
+        let answers = match expected.get(&v.stem()) {
+            Some(Expected { part1, part2 }) => format!("  expected {} {}", part1.as_deref().unwrap_or("-"), part2.as_deref().unwrap_or("-")),
+            None => String::new(),
+        };
+        let line = format!("{} {:<8}{}", if chosen { '*' } else { ' ' }, v.stem(), answers);
+        println!("{}", line.trim_end());
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn count(input: &str) -> String {
+        input.len().to_string()
+    }
+
+    fn shout(input: &str) -> String {
+        input.to_uppercase()
+    }
+
+    #[test]
+    fn test_day() {
+        let day = Day { day: 7, part1: count, part2: shout, visualize: None };
+        let config = Config::from_toml("[day.7]\ninput = \"a\"", Path::new("/repo")).unwrap();
+
+        assert_eq!(day.name(), "day07");
+        assert_eq!(day.input_path(&config, 2025, None), Path::new("/repo/years/2025/input/07.txt"));
+        assert_eq!(day.input_path(&config, 2025, Some("a")), Path::new("/repo/years/2025/input/07a.txt"));
+        assert!(day.read_input(&config, 2025).unwrap_err().contains("07a.txt"));
+
+        let [(p1, _), (p2, _)] = day.solve("abc");
+        assert_eq!((p1.as_str(), p2.as_str()), ("3", "ABC"));
+    }
+
+    #[test]
+    fn test_variant_names() {
+        let v = |day, name: &str| Some(Variant { day, name: name.to_owned() });
+
+        assert_eq!(Variant::from_file_name("07.txt"), v(7, ""));
+        assert_eq!(Variant::from_file_name("07a.txt"), v(7, "a"));
+        assert_eq!(Variant::from_file_name("12large_2.txt"), v(12, "large_2"));
+        assert_eq!(v(7, "a").unwrap().stem(), "07a");
+
+        for name in ["07a.md", "7.txt", "x7a.txt", "07 a.txt", "expected.toml"] {
+            assert_eq!(Variant::from_file_name(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_variants_and_expected() {
+        let dir = std::env::temp_dir().join(format!("advent-run-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, text) in [("07.txt", "abcd"), ("07a.txt", "ab"), ("03b.txt", "x"), ("notes.txt", ""), ("08.md", "")] {
+            fs::write(dir.join(name), text).unwrap();
+        }
+        fs::write(dir.join(EXPECTED_FILE), "[07a]\npart1 = \"2\"\npart2 = \"AB\"\n\n[07]\npart1 = \"5\"\n\n[09]\npart1 = \"1\"\n").unwrap();
+
+        fs::write(dir.join("05.txt"), "1\n2\r\n3\n").unwrap();
+        assert_eq!(read_input_file(&dir.join("05.txt")).unwrap(), "1\r\n2\r\n3\r\n");
+        fs::remove_file(dir.join("05.txt")).unwrap();
+
+        let stems = variants(&dir).unwrap().iter().map(Variant::stem).collect::<Vec<_>>();
+        assert_eq!(stems, ["03b", "07", "07a"]);
+
+        let expected = load_expected(&dir.join(EXPECTED_FILE)).unwrap();
+        assert_eq!(expected["07"], Expected { part1: Some("5".to_owned()), part2: None });
+        assert!(load_expected(&dir.join("missing.toml")).unwrap().is_empty());
+
+        let days = [Day { day: 7, part1: count, part2: shout, visualize: None }];
+        assert_eq!(check_expected(&days, &dir, &expected), [
+            "07 part 1: expected 5, got 4",
+            "09: no solution for day 9",
+        ]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
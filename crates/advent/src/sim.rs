@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Applies `step` until the state stops changing, returning the final state and the
+/// number of steps that changed it.
+pub fn fixpoint<S: PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> (S, usize) {
+    let mut state = initial;
+    let mut rounds = 0;
+
+    loop {
+        let next = step(&state);
+        if next == state {
+            return (state, rounds);
+        }
+        state = next;
+        rounds += 1;
+    }
+}
+
+/// Runs `step` over the state in place until it reports that nothing changed, returning
+/// the number of rounds that made a change.
+pub fn until_stable<S>(state: &mut S, mut step: impl FnMut(&mut S) -> bool) -> usize {
+    let mut rounds = 0;
+    while step(state) {
+        rounds += 1;
+    }
+    rounds
+}
+
+/// Processes items until none are left, where processing an item returns the items it
+/// may have affected. An item already waiting is not queued again. Returns the number
+/// of items processed.
+pub fn worklist<N, I>(initial: impl IntoIterator<Item = N>, mut process: impl FnMut(&N) -> I) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut queue = VecDeque::new();
+    let mut waiting = HashSet::new();
+
+    for item in initial {
+        if waiting.insert(item.clone()) {
+            queue.push_back(item);
+        }
+    }
+
+    let mut processed = 0;
+    while let Some(item) = queue.pop_front() {
+        waiting.remove(&item);
+        processed += 1;
+
+        for next in process(&item) {
+            if waiting.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    processed
+}
+
+/// Processes items a whole wave at a time, where each wave is every item returned by the
+/// wave before it, without repeats. Stops at the first empty wave and returns the number
+/// of waves processed.
+pub fn worklist_waves<N, I>(initial: impl IntoIterator<Item = N>, mut process: impl FnMut(&[N]) -> I) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let dedup = |items: &mut dyn Iterator<Item = N>| {
+        let mut seen = HashSet::new();
+        items.filter(|n| seen.insert(n.clone())).collect::<Vec<_>>()
+    };
+
+    let mut wave = dedup(&mut initial.into_iter());
+    let mut waves = 0;
+
+    while !wave.is_empty() {
+        waves += 1;
+        wave = dedup(&mut process(&wave).into_iter());
+    }
+
+    waves
+}
+
+/// The states of a simulation up to the point it first repeats.
+#[derive(Debug, Clone)]
+pub struct Cycle<S> {
+    /// Every distinct state in order, starting with the initial state.
+    pub states: Vec<S>,
+    /// Step at which the repeating part begins.
+    pub start: usize,
+    /// Number of steps before a state recurs.
+    pub period: usize,
+}
+
+impl<S> Cycle<S> {
+    /// The state after `n` steps, for any `n`.
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.start {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.period]
+        }
+    }
+}
+
+/// Steps a simulation until a state repeats. Never returns if the states never repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    loop {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let period = states.len() - start;
+            return Cycle { states, start, period };
+        }
+
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixpoint() {
+        // Repeatedly halving and rounding up settles on 1
+        let (state, rounds) = fixpoint(100u32, |&n| n.div_ceil(2));
+        assert_eq!(state, 1);
+        assert_eq!(rounds, 7);
+
+        let (state, rounds) = fixpoint(vec![3, 1, 2], |v| {
+            let mut v = v.clone();
+            if let Some(i) = (1..v.len()).find(|&i| v[i - 1] > v[i]) {
+                v.swap(i - 1, i);
+            }
+            v
+        });
+        assert_eq!(state, vec![1, 2, 3]);
+        assert_eq!(rounds, 2);
+    }
+
+    #[test]
+    fn test_until_stable() {
+        let mut v = vec![5, 0, 0, 0];
+        let rounds = until_stable(&mut v, |v| {
+            match (1..v.len()).find(|&i| v[i - 1] > v[i] + 1) {
+                Some(i) => { v[i - 1] -= 1; v[i] += 1; true }
+                None => false,
+            }
+        });
+        assert_eq!(v, vec![2, 2, 1, 0]);
+        assert_eq!(rounds, 4);
+    }
+
+    #[test]
+    fn test_worklist() {
+        // Spread a fire through a line of cells, only revisiting neighbours of cells that caught
+        let mut burning = [false, false, true, false, false, false];
+        let processed = worklist([2], |&i: &usize| {
+            let mut next = Vec::new();
+            for j in [i.wrapping_sub(1), i + 1] {
+                if j < burning.len() && !burning[j] {
+                    burning[j] = true;
+                    next.push(j);
+                }
+            }
+            next
+        });
+
+        assert!(burning.iter().all(|&b| b));
+        assert_eq!(processed, 6);
+    }
+
+    #[test]
+    fn test_worklist_dedups_waiting() {
+        let mut seen = Vec::new();
+        let processed = worklist([1, 1, 2], |&i: &u32| {
+            seen.push(i);
+            if i < 4 { vec![i + 1, i + 1] } else { vec![] }
+        });
+
+        assert_eq!(seen, vec![1, 2, 3, 4]);
+        assert_eq!(processed, 4);
+    }
+
+    #[test]
+    fn test_worklist_waves() {
+        // Every number spawns its double and its double plus one below 16, giving the
+        // levels of a binary heap
+        let mut levels = Vec::new();
+        let waves = worklist_waves([1u32], |wave| {
+            levels.push(wave.to_vec());
+            wave.iter().flat_map(|&n| [n * 2, n * 2 + 1, n * 2]).filter(|&n| n < 16).collect::<Vec<_>>()
+        });
+
+        assert_eq!(waves, 4);
+        assert_eq!(levels[1], vec![2, 3]);
+        assert_eq!(levels[3], (8..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // From 7 the states run 3, 6, 12, 9 and then back to 3
+        let cycle = find_cycle(7u64, |&n| if n == 7 { 3 } else { n * 2 % 15 });
+        assert_eq!(cycle.states, vec![7, 3, 6, 12, 9]);
+        assert_eq!(cycle.start, 1);
+        assert_eq!(cycle.period, 4);
+
+        let brute = |n: usize| (0..n).fold(7u64, |s, _| if s == 7 { 3 } else { s * 2 % 15 });
+        for n in [0, 1, 4, 5, 6, 17, 1_000] {
+            assert_eq!(*cycle.state_at(n), brute(n));
+        }
+        assert_eq!(*cycle.state_at(1_000_000_000_000), 9);
+    }
+}
@@ -0,0 +1,6 @@
+use advent2025::{DAYS, YEAR};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    advent::run::main(YEAR, DAYS, 2)
+}
@@ -1,23 +1,24 @@
 
 use advent::*;
+use advent::parse::{lines, parse_input, step};
 
 #[derive(Debug, PartialEq)]
-enum DialDirection {
+pub enum DialDirection {
     Left,
     Right,
 }
 
-struct Dial {
-    position: i64,
-    zero_hits: i64,
+pub struct Dial {
+    pub position: i64,
+    pub zero_hits: i64,
 }
 
 impl Dial {
-    fn new(pos: i64) -> Self {
+    pub fn new(pos: i64) -> Self {
         Dial { position: pos, zero_hits: 0 }
     }
 
-    fn click_v1(&mut self, direction: &DialDirection, distance: i64) {
+    pub fn click_v1(&mut self, direction: &DialDirection, distance: i64) {
         let dist_factor = Dial::dist_factor(direction);
         let new_pos = self.position + (distance * dist_factor);
 
@@ -28,7 +29,7 @@ impl Dial {
         self.position = new_pos.rem_euclid(100);
     }
 
-    fn click_v2(&mut self, direction: &DialDirection, distance: i64) {
+    pub fn click_v2(&mut self, direction: &DialDirection, distance: i64) {
         let dist_factor = Dial::dist_factor(direction);
         let new_pos = self.position + (distance * dist_factor);
 
@@ -51,12 +52,12 @@ impl Dial {
     }
 }
 
-struct InputModel {
-    lines: Vec<(DialDirection, i64)>,
+pub struct InputModel {
+    pub lines: Vec<(DialDirection, i64)>,
 }
 
 impl InputModel {
-    fn find_password_v1(&self) -> i64 {
+    pub fn find_password_v1(&self) -> i64 {
         let mut dial = Dial::new(50);
 
         for (dir, dist) in &self.lines {
@@ -66,7 +67,7 @@ impl InputModel {
         dial.zero_hits
     }
 
-    fn find_password_v2(&self) -> i64 {
+    pub fn find_password_v2(&self) -> i64 {
         let mut dial = Dial::new(50);
 
         for (dir, dist) in &self.lines {
@@ -77,53 +78,86 @@ impl InputModel {
     }
 }
 
-impl From<&str> for InputModel {
-
-    fn from(s: &str) -> Self {
-        fn to_dial_direction(s: &str) -> DialDirection {
-            if s.starts_with('L') {
-                DialDirection::Left
-            } else {
-                DialDirection::Right
-            }
+impl From<char> for DialDirection {
+    fn from(c: char) -> Self {
+        match c {
+            'L' => DialDirection::Left,
+            _ => DialDirection::Right,
         }
+    }
+}
 
-        fn to_dial_distance(s: &str) -> i64 {
-            s[1..].trim().parse::<i64>().unwrap()
-        }
+impl From<&str> for InputModel {
 
-        let lines = input_as_lines(s).iter()
-            .map(|a| (to_dial_direction(a), to_dial_distance(a)))
+    fn from(s: &str) -> Self {
+        let lines = parse_input(s, lines(step("LR")))
+            .expect("Unable to parse dial instructions")
+            .into_iter()
+            .map(|(dir, dist)| (DialDirection::from(dir), dist))
             .collect::<Vec<_>>();
 
         InputModel { lines }
     }
 }
 
-fn default_input() -> &'static str {
+pub fn default_input() -> &'static str {
     include_input!(01)
 }
 
-pub fn part1() -> String {
-    let model = InputModel::from(default_input());
+pub fn parse(input: &str) -> InputModel {
+    InputModel::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let model = parse(input);
 
     model.find_password_v1().to_string()
 }
 
-pub fn part2() -> String {
-    let model = InputModel::from(default_input());
+pub fn part2(input: &str) -> String {
+    let model = parse(input);
 
     model.find_password_v2().to_string()
 }
 
-fn main() {
-    println!("{}", part1());
-    println!("{}", part2());
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent::generate::{differential, join_lines};
+    use rand::Rng;
+    use rand::rngs::StdRng;
+
+    // Dial instructions, some turning several times round
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        join_lines((0..size).map(|_| {
+            let dir = if rng.random_bool(0.5) { 'L' } else { 'R' };
+            let dist = if rng.random_bool(0.2) { rng.random_range(100..1_000) } else { rng.random_range(1..100) };
+            format!("{}{}", dir, dist)
+        }))
+    }
+
+    // Turns the dial one click at a time
+    fn brute_force(input: &str) -> (i64, i64) {
+        let (mut pos, mut landed, mut passed) = (50, 0, 0);
+
+        for (dir, dist) in InputModel::from(input).lines {
+            for _ in 0..dist {
+                pos = (pos + Dial::dist_factor(&dir)).rem_euclid(100);
+                passed += (pos == 0) as i64;
+            }
+            landed += (pos == 0) as i64;
+        }
+
+        (landed, passed)
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(200, 40, generate, |input| {
+            let model = InputModel::from(input);
+            (model.find_password_v1(), model.find_password_v2())
+        }, brute_force);
+    }
 
     #[test]
     fn test_input_model_from() {
@@ -225,11 +259,11 @@ mod tests {
 
     #[test]
     fn solve_part1() {
-        assert_eq!(part1(), "1150");
+        assert_eq!(part1(default_input()), "1150");
     }
 
     #[test]
     fn solve_part2() {
-        assert_eq!(part2(), "6738");
+        assert_eq!(part2(default_input()), "6738");
     }
 }
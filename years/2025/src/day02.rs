@@ -0,0 +1,209 @@
+
+use advent::*;
+use advent::parse::{csv, parse_input, range};
+
+pub struct ProductRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+pub struct InputModel {
+    pub ranges: Vec<ProductRange>,
+}
+
+impl From<&str> for InputModel {
+    fn from(s: &str) -> Self {
+        let ranges = parse_input(s, csv(range))
+            .expect("Unable to parse product ranges")
+            .into_iter()
+            .map(|(start, end)| ProductRange { start, end })
+            .collect();
+        InputModel { ranges }
+    }
+}
+
+impl From<&str> for ProductRange {
+    fn from(s: &str) -> Self {
+        let (start, end) = parse_input(s, range).expect("Unable to parse product range");
+        ProductRange { start, end }
+    }
+}
+
+impl ProductRange {
+    pub fn is_valid_id(id: i64, part2: bool) -> bool {
+        match part2 {
+            false => Self::is_valid_id_part1(id),
+            true => Self::is_valid_id_part2(id),
+        }
+    }
+
+    pub fn is_valid_id_part1(id: i64) -> bool {
+        let s = id.to_string();
+
+        if s.len().is_multiple_of(2) {
+            let half_len = s.len() / 2;
+            let (first_half, second_half) = s.split_at(half_len);
+            return first_half != second_half;
+        }
+
+        true
+    }
+
+    pub fn is_valid_id_part2(id: i64) -> bool {
+        let s = id.to_string();
+        !(1..=s.len() / 2).any(|repeat_len| ProductRange::repeats_at(&s, repeat_len))
+    }
+
+    pub fn repeats_at(s: &str, repeat_len: usize) -> bool {
+        s.len() > repeat_len &&
+            s.len().is_multiple_of(repeat_len) &&
+            s == s[0..repeat_len].repeat(s.len() / repeat_len)
+    }
+
+}
+
+impl InputModel {
+
+    pub fn sum_invalid_ids(&self, part2: bool) -> i64 {
+        par::sum(&self.ranges, |range| {
+            (range.start..=range.end)
+                .filter(|&id| !ProductRange::is_valid_id(id, part2))
+                .sum::<i64>()
+        })
+    }
+}
+
+pub fn default_input() -> &'static str {
+    include_input!(02)
+}
+
+pub fn parse(input: &str) -> InputModel {
+    InputModel::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let model = parse(input);
+
+    model.sum_invalid_ids(false).to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    let model = parse(input);
+    model.sum_invalid_ids(true).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::generate::differential;
+    use rand::Rng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_product_range_from() {
+        let input = "10-20";
+        let range = ProductRange::from(input);
+
+        assert_eq!(range.start, 10);
+        assert_eq!(range.end, 20);
+    }
+
+    #[test]
+    fn test_input_model_from() {
+        let input = "1-3,5-7,10-15";
+        let model = InputModel::from(input);
+
+        assert_eq!(model.ranges.len(), 3);
+        assert_eq!(model.ranges[0].start, 1);
+        assert_eq!(model.ranges[0].end, 3);
+        assert_eq!(model.ranges[1].start, 5);
+        assert_eq!(model.ranges[1].end, 7);
+        assert_eq!(model.ranges[2].start, 10);
+        assert_eq!(model.ranges[2].end, 15);
+    }
+
+    #[test]
+    fn test_is_valid_id_part1() {
+        assert_eq_false(ProductRange::is_valid_id_part1(1212));
+        assert_eq_false(ProductRange::is_valid_id_part1(123123));
+        assert_eq_true(ProductRange::is_valid_id_part1(123456));
+        assert_eq_true(ProductRange::is_valid_id_part1(112233));
+    }
+
+    #[test]
+    fn test_is_valid_id_part2() {
+        assert_eq_false(ProductRange::is_valid_id_part2(1212));
+        assert_eq_false(ProductRange::is_valid_id_part2(123123));
+        assert_eq_false(ProductRange::is_valid_id_part2(121212));
+        assert_eq_false(ProductRange::is_valid_id_part2(123123123));
+        assert_eq_true(ProductRange::is_valid_id_part2(123456));
+        assert_eq_true(ProductRange::is_valid_id_part2(112233));
+        assert_eq_true(ProductRange::is_valid_id_part2(121213));
+        assert_eq_true(ProductRange::is_valid_id_part2(123123122));
+
+        // Repeats of six digits or more
+        assert_eq_false(ProductRange::is_valid_id_part2(123456123456));
+        assert_eq_false(ProductRange::is_valid_id_part2(12345671234567));
+        assert_eq_true(ProductRange::is_valid_id_part2(123456123457));
+    }
+
+    // Ranges of up to a thousand IDs, most of them around an ID made of a repeated unit
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let ranges = (0..rng.random_range(1..=size)).map(|_| {
+            let centre = if rng.random_bool(0.8) {
+                let unit = rng.random_range(1..=7);
+                let repeats = rng.random_range(2..=(14 / unit).max(2));
+                let digits = (0..unit).map(|i| char::from(b'0' + rng.random_range(if i == 0 { 1 } else { 0 }..10))).collect::<String>();
+                digits.repeat(repeats).parse::<i64>().unwrap()
+            } else {
+                rng.random_range(1..1_000_000_000_000)
+            };
+
+            let start = (centre - rng.random_range(0..500)).max(1);
+            format!("{}-{}", start, centre + rng.random_range(0..500))
+        });
+
+        ranges.collect::<Vec<_>>().join(",")
+    }
+
+    // An ID is a repeat exactly when it shows up inside itself doubled, away from the ends
+    fn brute_force(input: &str) -> (i64, i64) {
+        let (mut halves, mut repeats) = (0, 0);
+
+        for range in InputModel::from(input).ranges {
+            for id in range.start..=range.end {
+                let s = id.to_string();
+                let n = s.len();
+                if n % 2 == 0 && s[..n / 2] == s[n / 2..] {
+                    halves += id;
+                }
+                if (s.clone() + &s)[1..2 * n - 1].contains(&s) {
+                    repeats += id;
+                }
+            }
+        }
+
+        (halves, repeats)
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(60, 8, generate, |input| {
+            let model = InputModel::from(input);
+            (model.sum_invalid_ids(false), model.sum_invalid_ids(true))
+        }, brute_force);
+    }
+
+    fn assert_eq_false(value: bool) { assert!(!value); }
+    fn assert_eq_true(value: bool) { assert!(value); }
+
+    #[test]
+    fn solve_part1() {
+        assert_eq!(part1(default_input()), "53420042388");
+    }
+
+    #[test]
+    fn solve_part2() {
+        assert_eq!(part2(default_input()), "69553832684");
+    }
+}
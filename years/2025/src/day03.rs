@@ -1,27 +1,28 @@
 
 use advent::*;
+use advent::parse::{int_pair, parse_input};
 use std::fmt;
 
 #[derive(Clone, Copy)]
-struct Battery {
-    capacity: i64,
-    index: i64,
+pub struct Battery {
+    pub capacity: i64,
+    pub index: i64,
 }
 
 #[derive(Clone)]
-struct BatteryBank {
-    batteries: Vec<Battery>,
+pub struct BatteryBank {
+    pub batteries: Vec<Battery>,
 }
 
-struct InputModel {
-    banks: Vec<BatteryBank>,
+#[derive(ParseInput)]
+pub struct InputModel {
+    #[parse(lines)]
+    pub banks: Vec<BatteryBank>,
 }
 
 impl From<&str> for Battery {
     fn from(s: &str) -> Self {
-        let parts = s.split(',').collect::<Vec<&str>>();
-        let capacity = parts[0].trim().parse::<i64>().expect("Unable to parse battery capacity");
-        let index = parts[1].trim().parse::<i64>().expect("Unable to parse battery index");
+        let (capacity, index) = parse_input(s, int_pair).expect("Unable to parse battery");
         Battery { capacity, index }
     }
 }
@@ -41,27 +42,19 @@ impl fmt::Display for BatteryBank {
     }
 }
 
-impl From<&str> for InputModel {
-    fn from(s: &str) -> Self {
-        let bank_map = |line: String| BatteryBank::from(line.as_str());
-        let banks: Vec<BatteryBank> = input_as_lines(s).into_iter().map(bank_map).collect();
-        InputModel { banks }
-    }
-}
-
 impl BatteryBank {
-    fn max_joltage(&self, part2: bool) -> i64 {
+    pub fn max_joltage(&self, part2: bool) -> i64 {
         match part2 {
             false => self.max_joltage_part1(),
             true => self.max_joltage_part2(),
         }
     }
 
-    fn max_joltage_part1(&self) -> i64 {
+    pub fn max_joltage_part1(&self) -> i64 {
         self.find_highest(2)
     }
 
-    fn max_joltage_part2(&self) -> i64 {
+    pub fn max_joltage_part2(&self) -> i64 {
         self.find_highest(12)
     }
 
@@ -96,29 +89,32 @@ impl BatteryBank {
 }
 
 
-fn default_input() -> &'static str {
+pub fn default_input() -> &'static str {
     include_input!(03)
 }
 
-pub fn part1() -> String {
-    let model = InputModel::from(default_input());
-
-    model.banks.iter().map(|b| b.max_joltage(false)).sum::<i64>().to_string()
+pub fn parse(input: &str) -> InputModel {
+    InputModel::from(input)
 }
 
-pub fn part2() -> String {
-    let model = InputModel::from(default_input());
-    model.banks.iter().map(|b| b.max_joltage(true)).sum::<i64>().to_string()
+pub fn part1(input: &str) -> String {
+    let model = parse(input);
+
+    par::sum(&model.banks, |b| b.max_joltage(false)).to_string()
 }
 
-fn main() {
-    println!("{}", part1());
-    println!("{}", part2());
+pub fn part2(input: &str) -> String {
+    let model = parse(input);
+    par::sum(&model.banks, |b| b.max_joltage(true)).to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent::generate::{differential, join_lines};
+    use itertools::Itertools;
+    use rand::Rng;
+    use rand::rngs::StdRng;
 
     #[test]
     fn test_input_model_from() {
@@ -170,13 +166,44 @@ mod tests {
         assert_eq!(BatteryBank::from("818181911112111").find_highest(2), 92);
     }
 
+    // Banks of 12 to 17 batteries, with runs of a few digits to make ties likely
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        join_lines((0..size).map(|_| {
+            let len = rng.random_range(12..=12 + size.min(5));
+            let top = rng.random_range(2..=9);
+            (0..len).map(|_| char::from(b'0' + rng.random_range(1..=top))).collect::<String>()
+        }))
+    }
+
+    // Tries every way of choosing the batteries
+    fn brute_force(input: &str) -> (i64, i64) {
+        let best = |bank: &str, digits: usize| {
+            bank.chars()
+                .combinations(digits)
+                .map(|c| c.into_iter().collect::<String>().parse::<i64>().unwrap())
+                .max()
+                .unwrap()
+        };
+
+        input.split("\r\n").fold((0, 0), |(p1, p2), bank| (p1 + best(bank, 2), p2 + best(bank, 12)))
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(100, 12, generate, |input| {
+            let model = InputModel::from(input);
+            let total = |part2| model.banks.iter().map(|b| b.max_joltage(part2)).sum::<i64>();
+            (total(false), total(true))
+        }, brute_force);
+    }
+
     #[test]
     fn solve_part1() {
-        assert_eq!(part1(), "17100");
+        assert_eq!(part1(default_input()), "17100");
     }
 
     #[test]
     fn solve_part2() {
-        assert_eq!(part2(), "170418192256861");
+        assert_eq!(part2(default_input()), "170418192256861");
     }
 }
@@ -0,0 +1,372 @@
+
+use advent::*;
+use advent::bitgrid::BitGrid;
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum TileContent {
+    #[default]
+    Empty,
+    Roll,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Warehouse {
+    rolls: BitGrid,
+}
+
+// A roll can be reached when fewer than `threshold` rolls sit in its neighbourhood
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Rules {
+    pub threshold: usize,
+    pub neighbourhood: Neighbourhood,
+}
+
+#[derive(Debug, Clone)]
+pub struct Settled {
+    pub waves: Vec<Vec<Point32>>,
+    pub stable: Warehouse,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules { threshold: 4, neighbourhood: Neighbourhood::Surrounding }
+    }
+}
+
+impl Settled {
+    pub fn removed(&self) -> usize {
+        self.waves.iter().map(|w| w.len()).sum()
+    }
+}
+
+impl Warehouse {
+    pub fn width(&self) -> i32 { self.rolls.width() as i32 }
+    pub fn height(&self) -> i32 { self.rolls.height() as i32 }
+
+    /// The tile's content, with tiles off the floor counted as empty.
+    pub fn get_tile(&self, x: i32, y: i32) -> TileContent {
+        if self.is_roll(x, y) { TileContent::Roll } else { TileContent::Empty }
+    }
+
+    pub fn remove_roll(&mut self, x: i32, y: i32) {
+        if self.is_roll(x, y) {
+            self.rolls.set(x as usize, y as usize, false);
+        }
+    }
+
+    pub fn is_roll(&self, x: i32, y: i32) -> bool {
+        self.rolls.get(x as i64, y as i64)
+    }
+
+    pub fn is_accessible(&self, x: i32, y: i32, rules: Rules) -> bool {
+        let n = Warehouse::neighbours(Point32 { x, y }, rules.neighbourhood)
+            .filter(|p| self.is_roll(p.x, p.y))
+            .count();
+
+        n < rules.threshold
+    }
+
+    pub fn find_accessible_rolls(&self, rules: Rules) -> Vec<Point32> {
+        let mut accessible_rolls = Vec::new();
+
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                if self.is_roll(x, y) && self.is_accessible(x, y, rules) {
+                    accessible_rolls.push(Point32 { x, y });
+                }
+            }
+        }
+
+        accessible_rolls
+    }
+
+    // Each wave removes every roll accessible at its start. The rolls are kept on a bit
+    // grid, so a wave is found a whole word of cells at a time.
+    pub fn settle(&self, rules: Rules) -> Settled {
+        let mut rolls = self.rolls.clone();
+        let mut waves = Vec::new();
+
+        loop {
+            let wave = rolls.and(&rolls.neighbours_fewer_than(rules.threshold, rules.neighbourhood));
+            if !wave.any() {
+                return Settled { waves, stable: Warehouse { rolls } };
+            }
+
+            waves.push(wave.ones().map(|(x, y)| Point32 { x: x as i32, y: y as i32 }).collect());
+            rolls = rolls.and_not(&wave);
+        }
+    }
+
+    pub fn neighbours(p: Point32, neighbourhood: Neighbourhood) -> impl Iterator<Item = Point32> {
+        neighbourhood.offsets().iter().map(move |&(dx, dy)| Point32 { x: p.x + dx as i32, y: p.y + dy as i32 })
+    }
+
+    pub fn rolls(&self) -> &BitGrid {
+        &self.rolls
+    }
+
+    pub fn roll_count(&self) -> usize {
+        self.rolls.count_ones()
+    }
+}
+
+impl From<&str> for Warehouse {
+    fn from(s: &str) -> Self {
+        let lines = input_as_lines(s);
+
+        let rolls = BitGrid::from_fn(lines[0].len(), lines.len(), |x, y| lines[y].as_bytes().get(x) == Some(&b'@'));
+        Warehouse { rolls }
+    }
+}
+
+pub fn default_input() -> &'static str {
+    include_input!(04)
+}
+
+pub fn parse(input: &str) -> Warehouse {
+    Warehouse::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let wh = parse(input);
+    wh.find_accessible_rolls(Rules::default()).len().to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    let wh = parse(input);
+    wh.settle(Rules::default()).removed().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::generate::{differential, join_lines};
+    use advent::sim::worklist_waves;
+    use rand::Rng;
+    use rand::rngs::StdRng;
+    use std::collections::HashMap;
+
+    fn sample_input() -> &'static str {
+        "..@@.@@@@.\r\n@@@.@.@.@@\r\n@@@@@.@.@@\r\n@.@@@@..@.\r\n@@.@@@@.@@\r\n.@@@@@@@.@\r\n.@.@.@.@@@\r\n@.@@@.@@@@\r\n.@@@@@@@@.\r\n@.@.@@@.@."
+    }
+
+    #[test]
+    fn test_warehouse_from() {
+        let wh = Warehouse::from(sample_input());
+
+        assert_eq!(wh.width(), 10);
+        assert_eq!(wh.height(), 10);
+        assert_eq!(wh.roll_count(), 71);
+
+        assert_eq!(wh.get_tile(-1, -1), TileContent::Empty);
+        assert_eq!(wh.get_tile(0, 0), TileContent::Empty);
+        assert_eq!(wh.get_tile(1, 0), TileContent::Empty);
+        assert_eq!(wh.get_tile(2, 0), TileContent::Roll);
+        assert_eq!(wh.get_tile(3, 0), TileContent::Roll);
+        assert_eq!(wh.get_tile(10, 0), TileContent::Empty);
+    }
+
+    #[test]
+    fn test_is_accessible() {
+        let wh = Warehouse::from(sample_input());
+
+        let assert_accessible_roll = |x, y, expected| assert_eq!(wh.is_roll(x, y) && wh.is_accessible(x, y, Rules::default()), expected);
+
+        assert_accessible_roll(0, 0, false);
+        assert_accessible_roll(1, 0, false);
+        assert_accessible_roll(2, 0, true);
+        assert_accessible_roll(3, 0, true);
+        assert_accessible_roll(4, 0, false);
+        assert_accessible_roll(5, 0, true);
+        assert_accessible_roll(6, 0, true);
+    }
+
+    #[test]
+    fn test_find_accessible_rolls() {
+        let wh = Warehouse::from(sample_input());
+        assert_eq!(wh.find_accessible_rolls(Rules::default()).len(), 13);
+    }
+
+    #[test]
+    fn test_settle() {
+        let wh = Warehouse::from(sample_input());
+        let settled = wh.settle(Rules::default());
+
+        assert_eq!(settled.removed(), 43);
+        assert_eq!(settled.waves.iter().map(|w| w.len()).collect::<Vec<_>>(), vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert_eq!(settled.waves[0], wh.find_accessible_rolls(Rules::default()));
+        assert_eq!(settled.stable.roll_count(), wh.roll_count() - 43);
+        assert!(settled.stable.find_accessible_rolls(Rules::default()).is_empty());
+    }
+
+    // The warehouse as it was kept before the bit grid, one map entry per tile
+    #[derive(Clone)]
+    struct TileMap {
+        tiles: HashMap<Point32, TileContent>,
+        width: i32,
+        height: i32,
+    }
+
+    impl TileMap {
+        fn new(wh: &Warehouse) -> Self {
+            let tiles = (0..wh.height())
+                .flat_map(|y| (0..wh.width()).map(move |x| Point32 { x, y }))
+                .map(|p| (p, wh.get_tile(p.x, p.y)))
+                .collect();
+            TileMap { tiles, width: wh.width(), height: wh.height() }
+        }
+
+        fn is_roll(&self, x: i32, y: i32) -> bool {
+            self.tiles.get(&Point32 { x, y }) == Some(&TileContent::Roll)
+        }
+
+        fn remove_roll(&mut self, x: i32, y: i32) {
+            if let Some(tile) = self.tiles.get_mut(&Point32 { x, y }) {
+                *tile = TileContent::Empty;
+            }
+        }
+
+        fn is_accessible(&self, p: Point32, rules: Rules) -> bool {
+            Warehouse::neighbours(p, rules.neighbourhood).filter(|n| self.is_roll(n.x, n.y)).count() < rules.threshold
+        }
+
+        fn find_accessible_rolls(&self, rules: Rules) -> Vec<Point32> {
+            (0..self.height)
+                .flat_map(|y| (0..self.width).map(move |x| Point32 { x, y }))
+                .filter(|&p| self.is_roll(p.x, p.y) && self.is_accessible(p, rules))
+                .collect()
+        }
+    }
+
+    // The tile by tile implementation `settle` replaced, on the tile map it used, kept to
+    // check and benchmark it against. After the first wave only the neighbours of removed
+    // rolls are looked at.
+    fn settle_by_worklist(wh: &TileMap, rules: Rules) -> Vec<Vec<Point32>> {
+        let mut wh = wh.clone();
+        let mut waves = Vec::new();
+
+        worklist_waves(wh.find_accessible_rolls(rules), |candidates| {
+            let mut wave = candidates.iter()
+                .filter(|&&p| wh.is_roll(p.x, p.y) && wh.is_accessible(p, rules))
+                .copied()
+                .collect::<Vec<_>>();
+
+            wave.iter().for_each(|p| wh.remove_roll(p.x, p.y));
+
+            let next = wave.iter()
+                .flat_map(|&p| Warehouse::neighbours(p, rules.neighbourhood))
+                .filter(|n| wh.is_roll(n.x, n.y))
+                .collect::<Vec<_>>();
+
+            if !wave.is_empty() {
+                wave.sort_by_key(|p| (p.y, p.x));
+                waves.push(wave);
+            }
+            next
+        });
+
+        waves
+    }
+
+    // Removes every accessible roll at once until none are left, rescanning the whole grid
+    fn settle_by_rescan(wh: &Warehouse, rules: Rules) -> Vec<Vec<Point32>> {
+        let mut wh = wh.clone();
+        let mut waves = Vec::new();
+
+        loop {
+            let wave = wh.find_accessible_rolls(rules);
+            if wave.is_empty() {
+                return waves;
+            }
+            wave.iter().for_each(|p| wh.remove_roll(p.x, p.y));
+            waves.push(wave);
+        }
+    }
+
+    #[test]
+    fn test_settle_rules() {
+        let wh = Warehouse::from(sample_input());
+
+        for neighbourhood in [Neighbourhood::Orthogonal, Neighbourhood::Surrounding] {
+            for threshold in 0..=9 {
+                let rules = Rules { threshold, neighbourhood };
+                let waves = wh.settle(rules).waves;
+                assert_eq!(waves, settle_by_rescan(&wh, rules), "{:?}", rules);
+                assert_eq!(waves, settle_by_worklist(&TileMap::new(&wh), rules), "{:?}", rules);
+            }
+        }
+
+        let orthogonal = wh.settle(Rules { threshold: 2, neighbourhood: Neighbourhood::Orthogonal });
+        assert!(orthogonal.removed() < 43);
+        assert_eq!(wh.settle(Rules { threshold: 0, ..Rules::default() }).waves.len(), 0);
+        assert_eq!(wh.settle(Rules { threshold: 9, ..Rules::default() }).removed(), wh.roll_count());
+    }
+
+    // Grids up to 80 wide, to cross a word boundary in the bit grid, at varying densities
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let (width, height) = (rng.random_range(1..=size * 4), rng.random_range(1..=size));
+        let density = rng.random_range(0.1..0.95);
+        join_lines((0..height).map(|_| (0..width).map(|_| if rng.random_bool(density) { '@' } else { '.' }).collect::<String>()))
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(120, 20, generate, |input| {
+            let settled = Warehouse::from(input).settle(Rules::default());
+            (settled.waves.first().map_or(0, |w| w.len()), settled.removed())
+        }, |input| {
+            let waves = settle_by_rescan(&Warehouse::from(input), Rules::default());
+            (waves.first().map_or(0, |w| w.len()), waves.iter().map(|w| w.len()).sum())
+        });
+    }
+
+    #[test]
+    fn test_rolls() {
+        let wh = Warehouse::from(sample_input());
+        let rolls = wh.rolls();
+
+        assert_eq!(rolls.count_ones(), wh.roll_count());
+        assert!(rolls.get(2, 0));
+        assert!(!rolls.get(1, 0));
+    }
+
+    // Run with `just bench day04`
+    #[test]
+    #[ignore]
+    fn bench_settle() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        let wh = Warehouse::from(default_input());
+        let map = TileMap::new(&wh);
+        let rules = Rules::default();
+        let runs = 20;
+
+        let start = Instant::now();
+        for _ in 1..runs {
+            black_box(settle_by_worklist(&map, rules));
+        }
+        let tiles = settle_by_worklist(&map, rules);
+        let tile_time = start.elapsed() / runs;
+
+        let start = Instant::now();
+        for _ in 1..runs {
+            black_box(wh.settle(rules));
+        }
+        let bits = wh.settle(rules).waves;
+        let bit_time = start.elapsed() / runs;
+
+        assert_eq!(tiles, bits);
+        println!("tiles: {:?} per run, bits: {:?} per run", tile_time, bit_time);
+    }
+
+    #[test]
+    fn solve_part1() {
+        assert_eq!(part1(default_input()), "1602");
+    }
+
+    #[test]
+    fn solve_part2() {
+        assert_eq!(part2(default_input()), "9518");
+    }
+}
@@ -1,59 +1,58 @@
 
 use advent::*;
+use advent::parse::{int, parse_input, range};
 use itertools::Itertools;
 
 type IngredientId = i64;
 
 #[derive(Debug, PartialEq, Eq)]
-struct IngredientItem {
-    id: IngredientId,
+pub struct IngredientItem {
+    pub id: IngredientId,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct IngredientRange {
-    start: IngredientId,
-    end: IngredientId,
+pub struct IngredientRange {
+    pub start: IngredientId,
+    pub end: IngredientId,
 }
 
-struct Inventory {
-    fresh: Vec<IngredientRange>,
+#[derive(ParseInput)]
+#[parse(sections)]
+pub struct Inventory {
+    #[parse(lines)]
+    pub fresh: Vec<IngredientRange>,
+    #[parse(lines)]
     items : Vec<IngredientItem>,
 }
 
-impl From<&str> for Inventory {
+impl From<&str> for IngredientRange {
     fn from(s: &str) -> Self {
-        let lines = input_as_lines(s).into_iter().filter(|x| !x.is_empty()).collect::<Vec<_>>();
-
-        let fresh = lines.iter().filter(|x| x.contains("-")).map(|line| {
-            let parts: Vec<&str> = line.split('-').collect();
-            let start = parts[0].parse::<IngredientId>().unwrap();
-            let end = parts[1].parse::<IngredientId>().unwrap();
-            IngredientRange { start, end }
-        }).collect();
-
-        let items = lines.iter().filter(|x| !x.contains("-")).map(|line| {
-            let id = line.parse::<IngredientId>().unwrap();
-            IngredientItem { id }
-        }).collect();
+        let (start, end) = parse_input(s, range).expect("Unable to parse fresh range");
+        IngredientRange { start, end }
+    }
+}
 
-        Inventory { fresh, items }
+impl From<&str> for IngredientItem {
+    fn from(s: &str) -> Self {
+        let id = parse_input(s, int).expect("Unable to parse ingredient");
+        IngredientItem { id }
     }
 }
 
 impl IngredientRange {
-    fn contains(&self, item: &IngredientItem) -> bool {
+    pub fn contains(&self, item: &IngredientItem) -> bool {
         item.id >= self.start && item.id <= self.end
     }
 }
 
 impl Inventory {
-    fn count_fresh_items(&self) -> i64 {
+    pub fn count_fresh_items(&self) -> i64 {
         self.items.iter().filter(|item| {
             self.fresh.iter().any(|range| range.contains(item))
         }).count() as i64
     }
 
-    fn count_fresh_ranges(&self) -> i64 {
+    pub fn count_fresh_ranges(&self) -> i64 {
         let mut consolid: Vec<(i64,i64)> = Vec::new();
         let mut prev_start = -1;
         let mut prev_end = -1;
@@ -80,28 +79,31 @@ impl Inventory {
     }
 }
 
-fn default_input() -> &'static str {
+pub fn default_input() -> &'static str {
     include_input!(05)
 }
 
-pub fn part1() -> String {
-    let inv = Inventory::from(default_input());
-    inv.count_fresh_items().to_string()
+pub fn parse(input: &str) -> Inventory {
+    Inventory::from(input)
 }
 
-pub fn part2() -> String {
-    let inv = Inventory::from(default_input());
-    inv.count_fresh_ranges().to_string()
+pub fn part1(input: &str) -> String {
+    let inv = parse(input);
+    inv.count_fresh_items().to_string()
 }
 
-fn main() {
-    println!("{}", part1());
-    println!("{}", part2());
+pub fn part2(input: &str) -> String {
+    let inv = parse(input);
+    inv.count_fresh_ranges().to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent::generate::{differential, join_lines};
+    use rand::Rng;
+    use rand::rngs::StdRng;
+    use std::collections::HashSet;
 
     #[test]
     fn test_input_model_from() {
@@ -121,6 +123,15 @@ mod tests {
         assert_eq!(inv.items[5], IngredientItem { id: 32 });
     }
 
+    #[test]
+    fn test_input_model_sections() {
+        let input = "-5--3\r\n2-4\r\n\r\n-4\r\n3\r\n-10";
+        let inv = Inventory::from(input);
+        assert_eq!(inv.fresh, vec![IngredientRange { start: -5, end: -3 }, IngredientRange { start: 2, end: 4 }]);
+        assert_eq!(inv.items, vec![IngredientItem { id: -4 }, IngredientItem { id: 3 }, IngredientItem { id: -10 }]);
+        assert_eq!(inv.count_fresh_items(), 2);
+    }
+
     #[test]
     fn test_count_fresh_items() {
         let input = "3-5\r\n10-14\r\n16-20\r\n12-18\r\n\r\n1\r\n5\r\n8\r\n11\r\n17\r\n32";
@@ -137,14 +148,44 @@ mod tests {
         assert_eq!(count, 14); // consolidated ranges are 3-5, 10-20
     }
 
+    // Ranges and items over a span small enough that ranges nest, overlap and touch often,
+    // reaching below zero now and then
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let span = size as i64 * 10;
+        let low = if rng.random_bool(0.3) { -span } else { 0 };
+
+        let ranges = (0..rng.random_range(1..=size)).map(|_| {
+            let start = rng.random_range(low..span);
+            format!("{}-{}", start, start + rng.random_range(0..=span / 3))
+        }).collect::<Vec<_>>();
+        let items = (0..rng.random_range(1..=size * 2)).map(|_| rng.random_range(low..span + span / 3).to_string()).collect::<Vec<_>>();
+
+        format!("{}\r\n\r\n{}", join_lines(ranges), join_lines(items))
+    }
+
+    // Lists every fresh ID
+    fn brute_force(input: &str) -> (i64, i64) {
+        let inv = Inventory::from(input);
+        let fresh = inv.fresh.iter().flat_map(|r| r.start..=r.end).collect::<HashSet<_>>();
+        let items = inv.items.iter().filter(|i| fresh.contains(&i.id)).count();
+        (items as i64, fresh.len() as i64)
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(200, 20, generate, |input| {
+            let inv = Inventory::from(input);
+            (inv.count_fresh_items(), inv.count_fresh_ranges())
+        }, brute_force);
+    }
 
     #[test]
     fn solve_part1() {
-        assert_eq!(part1(), "739");
+        assert_eq!(part1(default_input()), "739");
     }
 
     #[test]
     fn solve_part2() {
-        assert_eq!(part2(), "344486348901788");
+        assert_eq!(part2(default_input()), "344486348901788");
     }
 }
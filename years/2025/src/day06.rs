@@ -2,21 +2,21 @@
 use advent::*;
 
 #[derive(Debug, Default, PartialEq, Eq)]
-enum MathOperator {
+pub enum MathOperator {
     #[default]
     Add,
     Multiply,
 }
  
 #[derive(Debug, Default, PartialEq, Eq)]
-struct MathProblem {
-    numbers: Vec<i64>,
-    operator: MathOperator,
+pub struct MathProblem {
+    pub numbers: Vec<i64>,
+    pub operator: MathOperator,
 }
 
-struct MathWorksheet {
-    part1_problems: Vec<MathProblem>,
-    part2_problems: Vec<MathProblem>,
+pub struct MathWorksheet {
+    pub part1_problems: Vec<MathProblem>,
+    pub part2_problems: Vec<MathProblem>,
 }
 
 impl TryFrom<Option<char>> for MathOperator {
@@ -99,7 +99,9 @@ impl MathWorksheet {
                 vt = vt[..vt.len()-1].trim();
             }
 
-            problem.numbers.push(vt.parse::<i64>().unwrap());
+            // A shorter number can leave a gap partway down a column
+            let digits = vt.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+            problem.numbers.push(digits.parse::<i64>().unwrap());
         }
 
         problems.push(problem);
@@ -123,7 +125,7 @@ impl MathWorksheet {
 }
 
 impl MathProblem {
-    fn solve(&self) -> i64 {
+    pub fn solve(&self) -> i64 {
         match self.operator {
             MathOperator::Add => self.numbers.iter().sum(),
             MathOperator::Multiply => self.numbers.iter().product(),
@@ -131,30 +133,32 @@ impl MathProblem {
     }
 }
 
-fn default_input() -> &'static str {
+pub fn default_input() -> &'static str {
     include_input!(06)
 }
 
-pub fn part1() -> String {
-    let ws = MathWorksheet::from(default_input());
+pub fn parse(input: &str) -> MathWorksheet {
+    MathWorksheet::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let ws = parse(input);
 
     ws.part1_problems.iter().map(|p| p.solve()).sum::<i64>().to_string()
 }
 
-pub fn part2() -> String {
-    let ws = MathWorksheet::from(default_input());
+pub fn part2(input: &str) -> String {
+    let ws = parse(input);
 
     ws.part2_problems.iter().map(|p| p.solve()).sum::<i64>().to_string()
 }
 
-fn main() {
-    println!("{}", part1());
-    println!("{}", part2());
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent::generate::{differential, join_lines};
+    use rand::Rng;
+    use rand::rngs::StdRng;
 
     #[test]
     fn test_worksheet_from() {
@@ -189,13 +193,75 @@ mod tests {
         assert_eq!(ws.part2_problems[3].solve(), 623 + 431 + 4);
     }
 
+    // Problems of 2 to 4 numbers of up to 4 digits, each number pushed to either side of
+    // its column
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let rows = rng.random_range(2..=4);
+        let mut lines = vec![String::new(); rows + 1];
+
+        for i in 0..size {
+            let numbers = (0..rows).map(|_| rng.random_range(1..10_000).to_string()).collect::<Vec<_>>();
+            let width = numbers.iter().map(|n| n.len()).max().unwrap();
+            let sep = if i == 0 { "" } else { " " };
+
+            for (line, n) in lines.iter_mut().zip(&numbers) {
+                let cell = if rng.random_bool(0.5) { format!("{:<width$}", n) } else { format!("{:>width$}", n) };
+                *line += &format!("{}{}", sep, cell);
+            }
+            let op = if rng.random_bool(0.5) { '+' } else { '*' };
+            lines[rows] += &format!("{}{:<width$}", sep, op);
+        }
+
+        join_lines(lines)
+    }
+
+    // Splits the sheet into problems at the blank columns, then reads each one across
+    // and down
+    fn brute_force(input: &str) -> (i64, i64) {
+        let rows = input.split("\r\n").map(|l| l.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let (ops, digits) = rows.split_last().unwrap();
+        let width = rows.iter().map(|r| r.len()).max().unwrap();
+        let blank = |x: usize| rows.iter().all(|r| r.get(x).is_none_or(|&c| c == ' '));
+
+        let mut blocks = Vec::new();
+        let mut start = None;
+        for x in 0..=width {
+            if x == width || blank(x) {
+                blocks.extend(start.take().map(|s| s..x));
+            } else if start.is_none() {
+                start = Some(x);
+            }
+        }
+
+        let (mut across, mut down) = (0, 0);
+        for block in blocks {
+            let multiply = ops[block.clone()].contains(&'*');
+            let apply = |ns: Vec<i64>| if multiply { ns.iter().product::<i64>() } else { ns.iter().sum() };
+            let number = |cs: Vec<char>| cs.iter().filter(|c| c.is_ascii_digit()).collect::<String>().parse::<i64>().unwrap();
+
+            across += apply(digits.iter().map(|r| number(r[block.clone()].to_vec())).collect());
+            down += apply(block.map(|x| number(digits.iter().map(|r| r[x]).collect())).collect());
+        }
+
+        (across, down)
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(150, 30, generate, |input| {
+            let ws = MathWorksheet::from(input);
+            let total = |problems: &[MathProblem]| problems.iter().map(|p| p.solve()).sum::<i64>();
+            (total(&ws.part1_problems), total(&ws.part2_problems))
+        }, brute_force);
+    }
+
     #[test]
     fn solve_part1() {
-        assert_eq!(part1(), "7644505810277");
+        assert_eq!(part1(default_input()), "7644505810277");
     }
 
     #[test]
     fn solve_part2() {
-        assert_eq!(part2(), "12841228084455");
+        assert_eq!(part2(default_input()), "12841228084455");
     }
 }
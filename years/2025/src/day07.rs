@@ -3,21 +3,21 @@ use advent::*;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq)]
-struct TachyonManifold {
-    grid: HashMap<(i64, i64), TachyonCell>,
-    width: i64,
-    height: i64,
-    debug: bool,
+pub struct TachyonManifold {
+    pub grid: HashMap<(i64, i64), TachyonCell>,
+    pub width: i64,
+    pub height: i64,
+    pub debug: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct TachyonCell {
-    cell_type: TachyonCellType,
-    timelines: i64,
+pub struct TachyonCell {
+    pub cell_type: TachyonCellType,
+    pub timelines: i64,
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum TachyonCellType {
+pub enum TachyonCellType {
     Empty,
     Origin,
     Splitter,
@@ -35,7 +35,7 @@ impl From<char> for TachyonCellType {
 
 impl TachyonManifold {
 
-    fn fire_beam(&mut self) -> (i64, i64) {
+    pub fn fire_beam(&mut self) -> (i64, i64) {
         let empty_cell = TachyonCell { cell_type: TachyonCellType::Empty, timelines: 0 };
 
         for y in 0..self.height {
@@ -72,7 +72,7 @@ impl TachyonManifold {
         }
     }
 
-    fn count_splits(&self) -> i64 {
+    pub fn count_splits(&self) -> i64 {
         let mut split_count = 0;
 
         for y in 1..self.height {
@@ -89,12 +89,12 @@ impl TachyonManifold {
         split_count
     }
 
-    fn count_timelines(&self) -> i64 {
+    pub fn count_timelines(&self) -> i64 {
         let fn_cell = |x| self.cell(x, self.height - 1).unwrap();
         (0..self.width).map(fn_cell).map(|c| c.timelines).sum::<i64>()
     }
 
-    fn cell(&self, x: i64, y: i64) -> Option<&TachyonCell> {
+    pub fn cell(&self, x: i64, y: i64) -> Option<&TachyonCell> {
         self.grid.get(&(x, y))
     }
 
@@ -167,37 +167,49 @@ impl From<&str> for TachyonManifold {
     }
 }
 
-fn default_input() -> &'static str {
+pub fn default_input() -> &'static str {
     include_input!(07)
 }
 
-fn sample_input() -> &'static str {
-    include_input!(07a)
+pub fn parse(input: &str) -> TachyonManifold {
+    TachyonManifold::from(input)
 }
 
-pub fn part1() -> String {
-    let mut mf = TachyonManifold::from(default_input());
+pub fn part1(input: &str) -> String {
+    let mut mf = parse(input);
     mf.fire_beam().0.to_string()
 }
 
-pub fn part2() -> String {
-    let mut mf = TachyonManifold::from(default_input());
+pub fn part2(input: &str) -> String {
+    let mut mf = parse(input);
     mf.fire_beam().1.to_string()
 }
 
-fn main() {
-    let _ = sample_input();
-    println!("{}", part1());
-    println!("{}", part2());
+/// Prints the manifold and its timeline counts row by row as the beam goes down.
+pub fn visualize(input: &str) {
+    let mut mf = parse(input);
+    mf.debug = true;
+    mf.fire_beam();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent::generate::{differential, join_lines};
+    use advent::run::read_input_file;
+    use rand::Rng;
+    use rand::rngs::StdRng;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    // The example from the puzzle text, kept as the `07a` input
+    fn example() -> String {
+        read_input_file(&Path::new(env!("CARGO_MANIFEST_DIR")).join("input").join("07a.txt")).unwrap()
+    }
 
     #[test]
     fn test_manifold_from() {
-        let mf = TachyonManifold::from(sample_input());
+        let mf = TachyonManifold::from(example().as_str());
         assert_eq!(mf.cell(0, 0).unwrap().cell_type, TachyonCellType::Empty);
         assert_eq!(mf.cell(7, 0).unwrap().cell_type, TachyonCellType::Origin);
         assert_eq!(mf.cell(7, 1).unwrap().cell_type, TachyonCellType::Empty);
@@ -207,7 +219,7 @@ mod tests {
 
     #[test]
     fn test_fire_beam() {
-        let mut mf = TachyonManifold::from(sample_input());
+        let mut mf = TachyonManifold::from(example().as_str());
         mf.fire_beam();
         assert_eq!(mf.cell(7, 0).unwrap().cell_type, TachyonCellType::Origin);
         assert_eq!(mf.cell(7, 1).unwrap().cell_type, TachyonCellType::Empty);
@@ -222,7 +234,7 @@ mod tests {
 
     #[test]
     fn test_count_sample() {
-        let mut mf = TachyonManifold::from(sample_input());
+        let mut mf = TachyonManifold::from(example().as_str());
         let (sc, tc) = mf.fire_beam();
         assert_eq!(sc, 21);
         assert_eq!(tc, 40);
@@ -261,13 +273,62 @@ mod tests {
         assert_eq!(tc, 12)
     }
 
+    // A manifold `size` splitter rows deep, with splitters kept off the edges and apart
+    // from each other
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let width = 2 * size + 3;
+        let mut rows = vec![vec!['.'; width]; 2 * size + 2];
+        rows[0][rng.random_range(1..width - 1)] = 'S';
+
+        for y in (2..rows.len()).step_by(2) {
+            let mut x = 1;
+            while x < width - 1 {
+                if rng.random_bool(0.4) {
+                    rows[y][x] = '^';
+                    x += 1;
+                }
+                x += 1;
+            }
+        }
+
+        join_lines(rows.iter().map(|r| r.iter().collect::<String>()))
+    }
+
+    // Follows the beams down a row at a time, keeping a count of timelines per column
+    fn brute_force(input: &str) -> (i64, i64) {
+        let rows = input.split("\r\n").map(|l| l.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let mut beams = HashMap::from([(rows[0].iter().position(|&c| c == 'S').unwrap(), 1)]);
+        let mut splitters = HashSet::new();
+
+        for (y, row) in rows.iter().enumerate().skip(1) {
+            let mut next = HashMap::new();
+            for (x, count) in beams {
+                if row[x] == '^' {
+                    splitters.insert((x, y));
+                    *next.entry(x - 1).or_insert(0) += count;
+                    *next.entry(x + 1).or_insert(0) += count;
+                } else {
+                    *next.entry(x).or_insert(0) += count;
+                }
+            }
+            beams = next;
+        }
+
+        (splitters.len() as i64, beams.values().sum())
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(100, 20, generate, |input| TachyonManifold::from(input).fire_beam(), brute_force);
+    }
+
     #[test]
     fn solve_part1() {
-        assert_eq!(part1(), "1555");
+        assert_eq!(part1(default_input()), "1555");
     }
 
     #[test]
     fn solve_part2() {
-        assert_eq!(part2(), "12895232295789");
+        assert_eq!(part2(default_input()), "12895232295789");
     }
 }
@@ -0,0 +1,369 @@
+
+
+use advent::*;
+use advent::mst::{Edge, Kruskal};
+use advent::parse::{int_triple, lines, parse_input};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct JunctionBox {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+pub struct Playground {
+    pub boxes: HashMap<(i64,i64,i64), JunctionBox>,
+}
+
+// Pairs of boxes are ordered by exact squared distance, with ties broken by the
+// coordinates of the lower box of the pair and then the higher box. This is a total
+// order, so the sequence of connections never depends on the order boxes are read in.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+pub struct PairKey {
+    pub dist_sq: i64,
+    pub near: JunctionBox,
+    pub far: JunctionBox,
+}
+
+#[derive(Debug)]
+pub struct Circuits {
+    pub connections: Vec<(JunctionBox, JunctionBox)>,
+    pub circuit_sizes: Vec<usize>,
+    pub final_connection: Option<(JunctionBox, JunctionBox)>,
+}
+
+impl From<&str> for Playground {
+    fn from(s: &str) -> Self {
+        let coords = parse_input(s, lines(int_triple)).expect("Unable to parse junction boxes");
+        let mut boxes = HashMap::new();
+
+        for (x, y, z) in coords {
+            boxes.insert((x, y, z), JunctionBox { x, y, z });
+        }
+
+        Playground { boxes }
+    }
+}
+
+impl Playground {
+
+    // Connecting the closest boxes first is Kruskal's algorithm over every pair of boxes.
+    // The connections that join two circuits form the minimum spanning tree, and the
+    // last of them is the one that leaves a single circuit.
+    pub fn find_circuits(&self, conn_max: usize) -> Circuits {
+        let boxes = self.sorted_boxes();
+        let edges = Playground::find_edges(&boxes);
+        let to_pair = |e: Edge<PairKey>| (boxes[e.from], boxes[e.to]);
+
+        let mut kruskal = Kruskal::new(boxes.len(), &edges);
+        let mut connections = kruskal.by_ref()
+            .take(conn_max)
+            .filter_map(|(e, joined)| joined.then_some(to_pair(e)))
+            .collect::<Vec<_>>();
+
+        let circuit_sizes = kruskal.sets().sizes();
+
+        connections.extend(kruskal.by_ref().filter_map(|(e, joined)| joined.then_some(to_pair(e))));
+
+        let final_connection = match kruskal.sets().count() {
+            1 => connections.last().copied(),
+            _ => None,
+        };
+
+        Circuits { connections, circuit_sizes, final_connection }
+    }
+
+    pub fn find_edges(boxes: &[JunctionBox]) -> Vec<Edge<PairKey>> {
+        let indices = (0..boxes.len()).collect::<Vec<_>>();
+        par::flat_map(&indices, |&i| {
+            (i + 1..boxes.len())
+                .map(|j| Edge::new(i, j, PairKey::new(&boxes[i], &boxes[j])))
+                .collect()
+        })
+    }
+
+    pub fn sorted_boxes(&self) -> Vec<JunctionBox> {
+        self.boxes.values().copied().sorted().collect::<Vec<_>>()
+    }
+
+    #[allow(dead_code)]
+    fn print_combos(&self) {
+        let boxes = self.sorted_boxes();
+        Playground::find_edges(&boxes).iter()
+            .sorted_by_key(|e| e.weight)
+            .for_each(|e| println!("{:?} {:?} {}", boxes[e.from], boxes[e.to], boxes[e.from].dist(&boxes[e.to])));
+    }
+}
+
+impl PairKey {
+    pub fn new(b1: &JunctionBox, b2: &JunctionBox) -> Self {
+        PairKey { dist_sq: b1.dist_sq(b2), near: *b1.min(b2), far: *b1.max(b2) }
+    }
+}
+
+impl Circuits {
+    pub fn circuit_product(&self) -> i64 {
+        self.circuit_sizes.iter().take(3).map(|&n| n as i64).product::<i64>()
+    }
+
+    pub fn final_product(&self) -> i64 {
+        self.final_connection.map(|(b1, b2)| b1.x * b2.x).unwrap_or(0)
+    }
+
+    #[allow(dead_code)]
+    fn print_connections(&self) {
+        self.connections.iter().for_each(|(b1, b2)| println!("{:?} -> {:?}", b1, b2));
+    }
+}
+
+impl JunctionBox {
+    pub fn dist(&self, j: &JunctionBox) -> f64 {
+        let p = &[self.x as f64, self.y as f64, self.z as f64];
+        let q = &[j.x as f64, j.y as f64, j.z as f64];
+        p.iter().zip(q.iter()).map(|(&x1, &x2)| (x2 - x1).powf(2.0)).sum::<f64>().sqrt()
+    }
+
+    pub fn dist_sq(&self, j: &JunctionBox) -> i64 {
+        (self.x - j.x).pow(2) + (self.y - j.y).pow(2) + (self.z - j.z).pow(2)
+    }
+}
+
+pub fn default_input() -> &'static str {
+    include_input!(08)
+}
+
+pub fn sample_input() -> &'static str {
+    concat!(
+    "162,817,812\r\n",
+    "57,618,57\r\n",
+    "906,360,560\r\n",
+    "592,479,940\r\n",
+    "352,342,300\r\n",
+    "466,668,158\r\n",
+    "542,29,236\r\n",
+    "431,825,988\r\n",
+    "739,650,466\r\n",
+    "52,470,668\r\n",
+    "216,146,977\r\n",
+    "819,987,18\r\n",
+    "117,168,530\r\n",
+    "805,96,715\r\n",
+    "346,949,466\r\n",
+    "970,615,88\r\n",
+    "941,993,340\r\n",
+    "862,61,35\r\n",
+    "984,92,344\r\n",
+    "425,690,689"
+    )
+}
+
+pub fn parse(input: &str) -> Playground {
+    Playground::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let pg = parse(input);
+    pg.find_circuits(1000).circuit_product().to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    let pg = parse(input);
+    pg.find_circuits(1000).final_product().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::generate::{differential, join_lines};
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_input_from() {
+        let input = "1,2,3\r\n4,5,6";
+        let model = Playground::from(input);
+
+        assert_eq!(model.boxes.len(), 2);
+        let k1: (i64,i64,i64) = (1,2,3);
+        let k2: (i64,i64,i64) = (4,5,6);
+        assert_eq!(model.boxes.get(&k1).map(|f| f.x), Some(1));
+        assert_eq!(model.boxes.get(&k1).map(|f| f.y), Some(2));
+        assert_eq!(model.boxes.get(&k1).map(|f| f.z), Some(3));
+        assert_eq!(model.boxes.get(&k2).map(|f| f.x), Some(4));
+        assert_eq!(model.boxes.get(&k2).map(|f| f.y), Some(5));
+        assert_eq!(model.boxes.get(&k2).map(|f| f.z), Some(6));
+    }
+
+    #[test]
+    fn test_jbox_dist() {
+        let j1 = JunctionBox { x: 162, y: 817, z: 812 };
+        let j2 = JunctionBox { x: 431, y: 825, z: 988 };
+
+        let jd1 = j1.dist(&j2);
+        let jd2 = j2.dist(&j1);
+        assert!(jd1 - jd1 < 0.001);
+        assert!((321.56 - jd1).abs() < 0.001);
+        assert!((321.56 - jd2).abs() < 0.001);
+        assert_eq!(j1.dist_sq(&j2), 103_401);
+    }
+
+    #[test]
+    fn test_find_edges() {
+        let boxes = Playground::from(sample_input()).sorted_boxes();
+        let edges = Playground::find_edges(&boxes);
+
+        let sequential = (0..boxes.len()).tuple_combinations().collect::<Vec<(usize, usize)>>();
+        assert_eq!(edges.iter().map(|e| (e.from, e.to)).collect::<Vec<_>>(), sequential);
+        assert!(edges.iter().all(|e| e.weight == PairKey::new(&boxes[e.from], &boxes[e.to])));
+    }
+
+    #[test]
+    fn test_find_circuits() {
+        let pg = Playground::from(sample_input());
+        let circuits = pg.find_circuits(10);
+
+        assert_eq!(&circuits.circuit_sizes[..3], &[5, 4, 2]);
+        assert_eq!(circuits.circuit_product(), 40);
+        assert_eq!(circuits.connections.len(), 19);
+    }
+
+    #[test]
+    fn test_final_connection() {
+        let pg = Playground::from(sample_input());
+        let circuits = pg.find_circuits(10);
+
+        let (b1, b2) = circuits.final_connection.unwrap();
+        assert_eq!(b1.x * b2.x, 25272);
+        assert_eq!(circuits.final_product(), 25272);
+        assert_eq!(circuits.connections.last(), circuits.final_connection.as_ref());
+    }
+
+    #[test]
+    fn test_pair_key_order() {
+        let origin = JunctionBox { x: 0, y: 0, z: 0 };
+        let bx = JunctionBox { x: 1, y: 0, z: 0 };
+        let by = JunctionBox { x: 0, y: 1, z: 0 };
+        let far = JunctionBox { x: 0, y: 0, z: 2 };
+
+        assert_eq!(PairKey::new(&bx, &origin), PairKey::new(&origin, &bx));
+        assert!(PairKey::new(&origin, &by) < PairKey::new(&origin, &bx));
+        assert!(PairKey::new(&origin, &bx) < PairKey::new(&origin, &far));
+    }
+
+    fn shuffled_input(input: &str, rng: &mut StdRng) -> String {
+        let mut lines = input_as_lines(input);
+        lines.shuffle(rng);
+        lines.join("\r\n")
+    }
+
+    fn lattice_input() -> String {
+        // Every neighbouring pair is exactly 10 apart, so nearly every connection is a tie
+        (0..27).map(|i| format!("{},{},{}", (i % 3) * 10, (i / 3 % 3) * 10, (i / 9) * 10)).join("\r\n")
+    }
+
+    fn assert_shuffle_invariant(input: &str, conn_max: usize) {
+        let mut rng = StdRng::seed_from_u64(2025);
+        let expected = Playground::from(input).find_circuits(conn_max);
+
+        for _ in 0..25 {
+            let pg = Playground::from(shuffled_input(input, &mut rng).as_str());
+            let circuits = pg.find_circuits(conn_max);
+
+            assert_eq!(circuits.circuit_sizes, expected.circuit_sizes);
+            assert_eq!(circuits.connections, expected.connections);
+            assert_eq!(circuits.final_connection, expected.final_connection);
+            assert_eq!(circuits.circuit_product(), expected.circuit_product());
+            assert_eq!(circuits.final_product(), expected.final_product());
+        }
+    }
+
+    #[test]
+    fn test_shuffled_sample() {
+        assert_shuffle_invariant(sample_input(), 10);
+    }
+
+    #[test]
+    fn test_shuffled_ties() {
+        for conn_max in [5, 13, 20, 40] {
+            assert_shuffle_invariant(&lattice_input(), conn_max);
+        }
+    }
+
+    // Distinct boxes packed into a small cube, so many pairs are the same distance apart
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let side = size as i64 + 1;
+        let mut boxes = BTreeSet::new();
+        while boxes.len() < size + 1 {
+            boxes.insert((rng.random_range(0..side), rng.random_range(0..side), rng.random_range(0..side)));
+        }
+
+        let mut lines = boxes.iter().map(|(x, y, z)| format!("{},{},{}", x, y, z)).collect::<Vec<_>>();
+        lines.shuffle(rng);
+        join_lines(lines)
+    }
+
+    // Both answers for a few connection limits
+    fn limits(input: &str) -> Vec<usize> {
+        let n = input.split("\r\n").count();
+        vec![1, n / 2, n, 2 * n]
+    }
+
+    // Connects every pair in order of `PairKey`, relabelling a whole circuit on each join
+    fn brute_force(input: &str) -> Vec<(i64, i64)> {
+        let boxes = Playground::from(input).sorted_boxes();
+        let pairs = (0..boxes.len()).tuple_combinations()
+            .sorted_by_key(|&(i, j)| PairKey::new(&boxes[i], &boxes[j]))
+            .collect::<Vec<(usize, usize)>>();
+
+        let top_three = |labels: &[usize]| labels.iter().counts().into_values().sorted().rev().take(3).product::<usize>() as i64;
+
+        limits(input).into_iter().map(|conn_max| {
+            let mut labels = (0..boxes.len()).collect::<Vec<_>>();
+            let mut circuit_product = 0;
+            let mut final_product = 0;
+
+            for (n, &(i, j)) in pairs.iter().enumerate() {
+                if n == conn_max {
+                    circuit_product = top_three(&labels);
+                }
+                let (old, new) = (labels[j], labels[i]);
+                if old != new {
+                    labels.iter_mut().filter(|l| **l == old).for_each(|l| *l = new);
+                    if labels.iter().all(|&l| l == new) {
+                        final_product = boxes[i].x * boxes[j].x;
+                    }
+                }
+            }
+            if conn_max >= pairs.len() {
+                circuit_product = top_three(&labels);
+            }
+
+            (circuit_product, final_product)
+        }).collect()
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(80, 40, generate, |input| {
+            let pg = Playground::from(input);
+            limits(input).into_iter().map(|conn_max| {
+                let circuits = pg.find_circuits(conn_max);
+                (circuits.circuit_product(), circuits.final_product())
+            }).collect()
+        }, brute_force);
+    }
+
+    #[test]
+    fn solve_part1() {
+        assert_eq!(part1(default_input()), "42315");
+    }
+
+    #[test]
+    fn solve_part2() {
+        assert_eq!(part2(default_input()), "8079278220");
+    }
+}
@@ -0,0 +1,240 @@
+
+use advent::*;
+use advent::parse::{int_pair, lines, parse_input};
+use advent::polygon::{Orientation, Polygon, PolygonError, Rect};
+use itertools::Itertools;
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Copy)]
+pub struct Tile {
+    pub x: i64,
+    pub y: i64,
+}
+
+pub struct Theater {
+    pub tiles: Vec<Tile>,
+    pub floor: Polygon,
+}
+
+impl From<&str> for Tile {
+    fn from(s: &str) -> Self {
+        let (x, y) = parse_input(s, int_pair).expect("Unable to parse tile");
+        Tile { x, y }
+    }
+}
+
+impl From<&str> for Theater {
+    fn from(s: &str) -> Self {
+        let tiles = parse_input(s, lines(int_pair)).expect("Unable to parse red tiles")
+            .into_iter()
+            .map(|(x, y)| Tile { x, y })
+            .collect::<Vec<_>>();
+
+        let floor = Polygon::new(tiles.iter().map(|t| t.point()).collect());
+
+        Theater { tiles, floor }
+    }
+}
+
+impl Theater {
+
+    pub fn largest_rect_part1(&self) -> i64 {
+        self.get_cart_product().iter()
+            .map(|c| c.2).max().unwrap_or(0)
+    }
+
+    pub fn largest_rect_part2(&self) -> Result<i64, Vec<PolygonError>> {
+        self.validate()?;
+
+        // The red tiles outline the floor, and every tile within it is red or green
+        let coverage = self.floor.coverage();
+
+        let valid = par::filter_map(&self.get_cart_product(), |c| {
+            coverage.contains_rect(&Rect::from_corners(c.0.point(), c.1.point())).then_some(c.2)
+        });
+        Ok(valid.into_iter().max().unwrap_or(0))
+    }
+
+    pub fn validate(&self) -> Result<Orientation, Vec<PolygonError>> {
+        self.floor.validate()
+    }
+
+    fn get_cart_product(&self) -> Vec<(&Tile, &Tile, i64)> {
+        self.tiles.iter()
+            .cartesian_product(self.tiles.iter())
+            .filter(|(t1, t2)| (t1.x != t2.x || t1.y != t2.y) && t1.ge(t2))
+            .map(|tx| (tx.0, tx.1, tx.0.rect_size(tx.1)))
+            .collect::<Vec<_>>()
+    }
+}
+
+impl Tile {
+    pub fn rect_size(&self, t0: &Tile) -> i64 {
+        let dx = self.x.max(t0.x) - self.x.min(t0.x) + 1;
+        let dy = self.y.max(t0.y) - self.y.min(t0.y) + 1;
+        dx * dy
+    }
+
+    pub fn point(&self) -> Point64 {
+        Point64::new(self.x, self.y)
+    }
+}
+
+pub fn default_input() -> &'static str {
+    include_input!(09)
+}
+
+pub fn sample_input() -> &'static str {
+    concat!(
+    "7,1\r\n",
+    "11,1\r\n",
+    "11,7\r\n",
+    "9,7\r\n",
+    "9,5\r\n",
+    "2,5\r\n",
+    "2,3\r\n",
+    "7,3")
+}
+
+pub fn sample_input_b() -> &'static str {
+    concat!(
+    "5,1\r\n",
+    "5,7\r\n",
+    "2,7\r\n",
+    "2,5\r\n",
+    "4,5\r\n",
+    "4,3\r\n",
+    "2,3\r\n",
+    "2,1")
+}
+
+pub fn parse(input: &str) -> Theater {
+    Theater::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let th = parse(input);
+    th.largest_rect_part1().to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    let th = parse(input);
+    th.largest_rect_part2().expect("Red tiles do not form a simple loop").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::generate::{differential, join_lines, rectilinear_loop};
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_tile_from() {
+        let tile = Tile::from("34,56");
+        assert_eq!(tile.x, 34);
+        assert_eq!(tile.y, 56);
+    }
+
+    #[test]
+    fn test_theater_from() {
+        let th = Theater::from("34,56\r\n67,89\r\n67,100");
+        assert_eq!(th.tiles.len(), 3);
+        assert_eq!(th.tiles[0].x, 34);
+        assert_eq!(th.tiles[0].y, 56);
+        assert_eq!(th.tiles[1].x, 67);
+        assert_eq!(th.tiles[1].y, 89);
+        assert_eq!(th.tiles[2].x, 67);
+        assert_eq!(th.tiles[2].y, 100);
+
+        let edges = th.floor.edges();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[0].a, Point64::new(34, 56));
+        assert_eq!(edges[0].b, Point64::new(67, 89));
+        assert_eq!(edges[1].a, Point64::new(67, 89));
+        assert_eq!(edges[1].b, Point64::new(67, 100));
+        assert_eq!(edges[2].a, Point64::new(67, 100));
+        assert_eq!(edges[2].b, Point64::new(34, 56));
+    }
+
+    #[test]
+    fn test_theater_largest_rect_part1() {
+        let th = Theater::from(sample_input());
+        assert_eq!(th.largest_rect_part1(), 50);
+    }
+
+    #[test]
+    fn test_theater_largest_rect_part2a() {
+        let th = Theater::from(sample_input());
+        assert_eq!(th.largest_rect_part2(), Ok(24));
+    }
+
+    #[test]
+    fn test_theater_largest_rect_part2b() {
+        let th = Theater::from(sample_input_b());
+        assert_eq!(th.largest_rect_part2(), Ok(12));
+    }
+
+    #[test]
+    fn test_theater_largest_rect_notch() {
+        // No vertex or edge midpoint lies inside 8,4 - 26,2, yet its tiles above row 4 left of 24 are outside the loop
+        let th = Theater::from("8,4\r\n16,4\r\n16,6\r\n24,6\r\n24,2\r\n26,2\r\n26,7\r\n8,7");
+        assert_eq!(th.largest_rect_part2(), Ok(36));
+    }
+
+    #[test]
+    fn test_theater_validate() {
+        assert_eq!(Theater::from(sample_input()).validate(), Ok(Orientation::Clockwise));
+        assert_eq!(Theater::from(sample_input_b()).validate(), Ok(Orientation::Clockwise));
+        assert_eq!(Theater::from("1,1\r\n1,4\r\n3,4\r\n3,1").validate(), Ok(Orientation::CounterClockwise));
+
+        let th = Theater::from("34,56\r\n67,89\r\n67,100");
+        assert_eq!(th.largest_rect_part2(), Err(vec![PolygonError::TooFewVertices(3)]));
+
+        let th = Theater::from("1,1\r\n5,1\r\n5,5\r\n2,4");
+        let errors = th.largest_rect_part2().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(e, PolygonError::DiagonalEdge { .. })));
+
+        let th = Theater::from("1,1\r\n5,1\r\n5,5\r\n3,5\r\n3,0\r\n1,0");
+        assert_eq!(th.largest_rect_part2(), Err(vec![PolygonError::SelfIntersection { first: 0, second: 3 }]));
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        join_lines(rectilinear_loop(rng, size as i64).iter().map(|p| format!("{},{}", p.x, p.y)))
+    }
+
+    // Tries every pair of red tiles, checking every tile of the rectangle between them
+    fn brute_force(input: &str) -> (i64, i64) {
+        let th = Theater::from(input);
+        let (mut any, mut inside) = (0, 0);
+
+        for (a, b) in th.tiles.iter().tuple_combinations() {
+            let rect = Rect::from_corners(a.point(), b.point());
+            any = any.max(rect.area());
+
+            let tiles = (rect.min.x..=rect.max.x).cartesian_product(rect.min.y..=rect.max.y);
+            if tiles.map(|(x, y)| Point64::new(x, y)).all(|p| th.floor.contains(p)) {
+                inside = inside.max(rect.area());
+            }
+        }
+
+        (any, inside)
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(120, 12, generate, |input| {
+            let th = Theater::from(input);
+            (th.largest_rect_part1(), th.largest_rect_part2().unwrap())
+        }, brute_force);
+    }
+
+    #[test]
+    fn solve_part1() {
+        assert_eq!(part1(default_input()), "4781546175");
+    }
+
+    #[test]
+    fn solve_part2() {
+        assert_eq!(part2(default_input()), "1573359081");
+    }
+}
\ No newline at end of file
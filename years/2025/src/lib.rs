@@ -0,0 +1,54 @@
+//! Solutions to Advent of Code 2025, one module per day. Each day has `parse` to read an
+//! input into its domain types, and `part1` and `part2` to solve an input.
+
+use advent::run::Day;
+
+/// Day 1: Secret Entrance
+pub mod day01;
+/// Day 2: Gift Shop
+pub mod day02;
+/// Day 3: Lobby
+pub mod day03;
+/// Day 4: Printing Department
+pub mod day04;
+/// Day 5: Cafeteria
+pub mod day05;
+/// Day 6: Trash Compactor
+pub mod day06;
+/// Day 7: Laboratories
+pub mod day07;
+/// Day 8: Playground
+pub mod day08;
+/// Day 9: Movie Theater
+pub mod day09;
+
+pub const YEAR: u32 = 2025;
+
+pub const DAYS: &[Day] = &[
+    Day { day: 1, part1: day01::part1, part2: day01::part2, visualize: None },
+    Day { day: 2, part1: day02::part1, part2: day02::part2, visualize: None },
+    Day { day: 3, part1: day03::part1, part2: day03::part2, visualize: None },
+    Day { day: 4, part1: day04::part1, part2: day04::part2, visualize: None },
+    Day { day: 5, part1: day05::part1, part2: day05::part2, visualize: None },
+    Day { day: 6, part1: day06::part1, part2: day06::part2, visualize: None },
+    Day { day: 7, part1: day07::part1, part2: day07::part2, visualize: Some(day07::visualize) },
+    Day { day: 8, part1: day08::part1, part2: day08::part2, visualize: None },
+    Day { day: 9, part1: day09::part1, part2: day09::part2, visualize: None },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent::run::{check_expected, load_expected, EXPECTED_FILE};
+    use std::path::Path;
+
+    #[test]
+    fn test_expected_answers() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let expected = load_expected(&root.join("answer").join(EXPECTED_FILE)).unwrap();
+
+        assert!(!expected.is_empty());
+        let failures = check_expected(DAYS, &root.join("input"), &expected);
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,46 @@
+
+use advent::*;
+
+pub struct InputModel {
+    pub lines: Vec<String>,
+}
+
+impl From<&str> for InputModel {
+    fn from(s: &str) -> Self {
+        let lines = input_as_lines(s);
+        InputModel { lines }
+    }
+}
+
+pub fn default_input() -> &'static str {
+    include_input!(00)
+}
+
+pub fn parse(input: &str) -> InputModel {
+    InputModel::from(input)
+}
+
+pub fn part1(input: &str) -> String {
+    let model = parse(input);
+    model.lines.len().to_string()
+}
+
+pub fn part2(input: &str) -> String {
+    let model = parse(input);
+    model.lines.len().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_part1() {
+        assert_eq!(part1(default_input()), "zz");
+    }
+
+    #[test]
+    fn solve_part2() {
+        assert_eq!(part2(default_input()), "zz");
+    }
+}